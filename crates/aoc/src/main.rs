@@ -0,0 +1,84 @@
+mod new;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use cli_app::config::Workspace;
+use cli_app::{AllArgs, GenArgs, Registry, RunArgs};
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Runs any registered Advent of Code day", long_about = None)]
+struct Cli {
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+  /// Solve a single day.
+  Run {
+    /// Day number or crate name, e.g. `4` or `printing-department`.
+    #[arg(short, long)]
+    day:  String,
+    #[command(flatten)]
+    args: RunArgs,
+  },
+  /// Solve both parts of every registered day against its configured input.
+  All {
+    #[command(flatten)]
+    args: AllArgs,
+  },
+  /// Write a random input of a day, to stress-test and benchmark it.
+  Gen {
+    /// Day number or crate name, e.g. `4` or `printing-department`.
+    #[arg(short, long)]
+    day:  String,
+    #[command(flatten)]
+    args: GenArgs,
+  },
+  /// List the registered days.
+  List,
+  /// Create the crate of a new day and register it in the workspace.
+  New {
+    /// Crate name of the day, e.g. `printing-department`.
+    name: String,
+    /// Day of the advent calendar, defaults to the day after the last one.
+    #[arg(short, long)]
+    day:  Option<u8>,
+  },
+}
+
+fn registry() -> Registry {
+  Registry::new()
+    .register_with_generator(secret_entrance::App {})
+    .register_with_generator(gift_shop::App {})
+    .register_with_generator(lobby::App {})
+    .register_with_generator(printing_department::App)
+}
+
+fn main() -> Result<()> {
+  let registry = registry();
+
+  match Cli::parse().command {
+    Command::Run { day, args } => registry
+      .find(&day)
+      .with_context(|| format!("no solver registered for day `{day}`."))?
+      .run(args),
+    Command::All { args } => cli_app::run_all(&registry, args),
+    Command::Gen { day, args } => registry
+      .find_generator(&day)
+      .with_context(|| format!("no input generator registered for day `{day}`."))?
+      .generate(args),
+    Command::List => {
+      for solver in registry.iter() {
+        println!("Day {}: {}", solver.day(), solver.name());
+      }
+      Ok(())
+    }
+    Command::New { name, day } => {
+      let number = day.unwrap_or_else(|| registry.iter().map(|solver| solver.day()).max().unwrap_or(0) + 1);
+      new::scaffold(Workspace::discover()?.root(), &new::Day { number, name: &name })?;
+      println!("Created day {number} in `{name}`, run it with `cargo run -p aoc -- run --day {number}`.");
+      Ok(())
+    }
+  }
+}
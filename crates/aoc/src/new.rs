@@ -0,0 +1,217 @@
+//! `aoc new`: scaffolds the crate of the next day and wires it into the
+//! workspace, the root README and the `aoc` registry.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail, ensure};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const README_MD: &str = include_str!("../templates/README.md.tmpl");
+
+pub struct Day<'a> {
+  pub number: u8,
+  pub name:   &'a str,
+}
+
+impl Day<'_> {
+  /// `printing-department` becomes `Printing Department`.
+  fn title(&self) -> String {
+    self
+      .name
+      .split('-')
+      .map(|word| {
+        let mut chars = word.chars();
+        chars
+          .next()
+          .map(|first| first.to_uppercase().chain(chars).collect())
+          .unwrap_or_default()
+      })
+      .collect::<Vec<String>>()
+      .join(" ")
+  }
+
+  fn crate_ident(&self) -> String { self.name.replace('-', "_") }
+
+  fn render(&self, template: &str) -> String {
+    template
+      .replace("$NAME", self.name)
+      .replace("$CRATE", &self.crate_ident())
+      .replace("$TITLE", &self.title())
+      .replace("$DAY", &self.number.to_string())
+  }
+}
+
+/// Creates the crate of `day` in `root` and registers it everywhere a day is
+/// listed.
+pub fn scaffold(root: &Path, day: &Day) -> Result<()> {
+  ensure!(
+    day.name.starts_with(|c: char| c.is_ascii_lowercase())
+      && day
+        .name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'),
+    "`{}` is not a kebab-case crate name.",
+    day.name
+  );
+  let dir = root.join(day.name);
+  if dir.exists() {
+    bail!("`{}` already exists.", dir.display());
+  }
+
+  fs::create_dir_all(dir.join("src")).with_context(|| format!("cannot create `{}`.", dir.display()))?;
+  for (path, template) in [
+    ("Cargo.toml", CARGO_TOML),
+    ("README.md", README_MD),
+    ("src/lib.rs", LIB_RS),
+    ("src/main.rs", MAIN_RS),
+  ] {
+    write(&dir.join(path), &day.render(template))?;
+  }
+
+  edit(&root.join("Cargo.toml"), |manifest| add_member(manifest, day.name))?;
+  edit(&root.join("README.md"), |readme| add_readme_link(readme, day))?;
+  edit(&root.join("crates/aoc/Cargo.toml"), |manifest| {
+    add_dependency(manifest, day.name)
+  })?;
+  edit(&root.join("crates/aoc/src/main.rs"), |main| add_registration(main, day))?;
+  Ok(())
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+  fs::write(path, content).with_context(|| format!("cannot write `{}`.", path.display()))
+}
+
+fn edit(path: &Path, f: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+  let content = fs::read_to_string(path).with_context(|| format!("cannot read `{}`.", path.display()))?;
+  let edited = f(&content).with_context(|| format!("cannot edit `{}`.", path.display()))?;
+  write(path, &edited)
+}
+
+/// Adds `name` to the workspace `members` list, keeping the days sorted after
+/// the shared crates.
+fn add_member(manifest: &str, name: &str) -> Result<String> {
+  let Some(line) = manifest.lines().find(|line| line.starts_with("members = [")) else {
+    bail!("no single-line `members` list found.");
+  };
+  let mut members = line
+    .trim_start_matches("members = [")
+    .trim_end_matches(']')
+    .split(',')
+    .map(|member| member.trim().trim_matches('"').to_string())
+    .filter(|member| !member.is_empty())
+    .collect::<Vec<_>>();
+  members.push(name.to_string());
+  members.sort_by_key(|member| (!member.starts_with("crates/"), member.clone()));
+
+  let members = members.iter().map(|member| format!("\"{member}\"")).collect::<Vec<_>>();
+  Ok(manifest.replacen(line, &format!("members = [{}]", members.join(", ")), 1))
+}
+
+/// Appends a link to the day's README after the last day of the list.
+fn add_readme_link(readme: &str, day: &Day) -> Result<String> {
+  let mut lines = readme.lines().collect::<Vec<_>>();
+  let Some(last) = lines.iter().rposition(|line| line.starts_with("- [Day ")) else {
+    bail!("no list of days found.");
+  };
+  let link = format!("- [Day {}: {}](./{}/README.md)", day.number, day.title(), day.name);
+  lines.insert(last + 1, &link);
+  Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day's crate to the `[dependencies]` of the `aoc` runner, which are
+/// sorted by name.
+fn add_dependency(manifest: &str, name: &str) -> Result<String> {
+  let mut lines = manifest.lines().collect::<Vec<_>>();
+  let Some(start) = lines.iter().position(|&line| line == "[dependencies]") else {
+    bail!("no `[dependencies]` table found.");
+  };
+  let end = lines[start + 1..]
+    .iter()
+    .position(|line| line.trim().is_empty() || line.starts_with('['))
+    .map_or(lines.len(), |at| start + 1 + at);
+  let at = lines[start + 1..end]
+    .iter()
+    .position(|line| line.split(' ').next().is_some_and(|key| key > name))
+    .map_or(end, |at| start + 1 + at);
+
+  let dependency = format!("{name} = {{ path = \"../../{name}\" }}");
+  lines.insert(at, &dependency);
+  Ok(lines.join("\n") + "\n")
+}
+
+/// Registers the day's `App` after the last one registered by the runner, with
+/// or without a generator.
+fn add_registration(main: &str, day: &Day) -> Result<String> {
+  let mut lines = main.lines().collect::<Vec<_>>();
+  let Some(last) = lines
+    .iter()
+    .rposition(|line| line.trim_start().starts_with(".register"))
+  else {
+    bail!("no `.register(...)` call found.");
+  };
+  let indent = &lines[last][..lines[last].len() - lines[last].trim_start().len()];
+  let registration = format!("{indent}.register({}::App {{}})", day.crate_ident());
+  lines.insert(last + 1, &registration);
+  Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const DAY: Day = Day {
+    number: 5,
+    name:   "cafeteria",
+  };
+
+  #[test]
+  fn test_title() {
+    let day = Day {
+      number: 4,
+      name:   "printing-department",
+    };
+    assert_eq!(day.title(), "Printing Department");
+  }
+
+  #[test]
+  fn test_add_member() {
+    let manifest = "[workspace]\nmembers = [\"crates/aoc\", \"lobby\", \"secret-entrance\"]\n";
+    assert_eq!(
+      add_member(manifest, DAY.name).unwrap(),
+      "[workspace]\nmembers = [\"crates/aoc\", \"cafeteria\", \"lobby\", \"secret-entrance\"]\n"
+    );
+  }
+
+  #[test]
+  fn test_add_readme_link() {
+    let readme = "Intro\n\n- [Day 4: Printing Department](./printing-department/README.md)\n\n## Running\n";
+    assert_eq!(
+      add_readme_link(readme, &DAY).unwrap(),
+      "Intro\n\n- [Day 4: Printing Department](./printing-department/README.md)\n- [Day 5: \
+       Cafeteria](./cafeteria/README.md)\n\n## Running\n"
+    );
+  }
+
+  #[test]
+  fn test_add_dependency() {
+    let manifest = "[dependencies]\nanyhow = \"1\"\nlobby = { path = \"../../lobby\" }\n\n[features]\n";
+    assert_eq!(
+      add_dependency(manifest, DAY.name).unwrap(),
+      "[dependencies]\nanyhow = \"1\"\ncafeteria = { path = \"../../cafeteria\" }\nlobby = { path = \"../../lobby\" \
+       }\n\n[features]\n"
+    );
+  }
+
+  #[test]
+  fn test_add_registration() {
+    let main = "fn registry() -> Registry {\n  Registry::new()\n    .register_with_generator(lobby::App {})\n}\n";
+    assert_eq!(
+      add_registration(main, &DAY).unwrap(),
+      "fn registry() -> Registry {\n  Registry::new()\n    .register_with_generator(lobby::App {})\n    \
+       .register(cafeteria::App {})\n}\n"
+    );
+  }
+}
@@ -0,0 +1,98 @@
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::{App, Part, Variant};
+
+pub struct Stats {
+  min:    Duration,
+  median: Duration,
+  max:    Duration,
+}
+
+impl Stats {
+  fn from_samples(mut samples: Vec<Duration>) -> Self {
+    samples.sort();
+    Stats {
+      min:    samples[0],
+      median: samples[samples.len() / 2],
+      max:    samples[samples.len() - 1],
+    }
+  }
+}
+
+impl Display for Stats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "min {:>10.2?}  median {:>10.2?}  max {:>10.2?}",
+      self.min, self.median, self.max
+    )
+  }
+}
+
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+  let start = Instant::now();
+  let value = f();
+  (value, start.elapsed())
+}
+
+/// Parses and solves every planned part and variant `iterations` times,
+/// re-parsing the input before each solve.
+pub fn run<A: App>(
+  app: A,
+  input: &[u8],
+  params: &A::Params,
+  plan: &[(Part, Vec<Variant<A>>)],
+  iterations: u32,
+) -> Result<()> {
+  let runs = plan
+    .iter()
+    .flat_map(|(part, variants)| variants.iter().map(move |variant| (*part, *variant)))
+    .collect::<Vec<_>>();
+  let mut parse = Vec::new();
+  let mut solve = vec![Vec::new(); runs.len()];
+
+  for _ in 0..iterations {
+    for ((_, variant), samples) in runs.iter().zip(&mut solve) {
+      let (parsed, elapsed) = timed(|| app.parse_input(input));
+      parse.push(elapsed);
+      let (solved, elapsed) = timed(|| (variant.solve)(app, parsed?, params));
+      solved?;
+      samples.push(elapsed);
+    }
+  }
+
+  println!("Iterations: {iterations}");
+  println!("Parse: {}", Stats::from_samples(parse));
+  for ((part, variant), samples) in runs.into_iter().zip(solve) {
+    println!(
+      "Part {} ({}): {}",
+      part.name(),
+      variant.name,
+      Stats::from_samples(samples)
+    );
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_stats_from_samples() {
+    let stats = Stats::from_samples(vec![
+      Duration::from_millis(3),
+      Duration::from_millis(1),
+      Duration::from_millis(9),
+      Duration::from_millis(2),
+      Duration::from_millis(4),
+    ]);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_millis(3));
+    assert_eq!(stats.max, Duration::from_millis(9));
+  }
+}
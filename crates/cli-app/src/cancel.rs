@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Flag raised when a solver ran past `--timeout`.
+///
+/// The runner stops waiting for a timed out solver but cannot stop its thread,
+/// so long loops should poll [`CancellationToken::current`] and bail out early.
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+  /// Token of the solver running on this thread, which is never cancelled
+  /// outside of a run with `--timeout`.
+  pub fn current() -> Self { CURRENT.with(|token| token.borrow().clone()) }
+
+  pub fn is_cancelled(&self) -> bool { self.0.load(Ordering::Relaxed) }
+
+  pub(crate) fn cancel(&self) { self.0.store(true, Ordering::Relaxed) }
+
+  /// Makes `self` the current token while running `f`.
+  pub(crate) fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+    let outer = CURRENT.replace(self.clone());
+    let value = f();
+    CURRENT.set(outer);
+    value
+  }
+}
+
+thread_local! {
+  static CURRENT: RefCell<CancellationToken> = RefCell::default();
+}
+
+/// Parses a `--timeout` like `500ms`, `30s` or `2m`; a bare number is in
+/// seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+  let at = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+  let (value, unit) = s.split_at(at);
+  let value = value
+    .parse::<f64>()
+    .map_err(|_| format!("invalid duration `{s}`, expected e.g. `500ms`, `30s` or `2m`"))?;
+  let seconds = match unit {
+    "ms" => value / 1000.,
+    "" | "s" => value,
+    "m" => value * 60.,
+    _ => return Err(format!("unknown unit `{unit}`, expected `ms`, `s` or `m`")),
+  };
+  Duration::try_from_secs_f64(seconds).map_err(|err| format!("invalid duration `{s}`: {err}"))
+}
+
+/// Phase a solver was stuck in when it timed out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+  Parse,
+  Solve,
+}
+
+impl fmt::Display for Phase {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Phase::Parse => write!(f, "parsing"),
+      Phase::Solve => write!(f, "solving"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_parse_duration() {
+    assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+    assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+    assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+    assert_eq!(parse_duration("3"), Ok(Duration::from_secs(3)));
+    assert!(parse_duration("3h").is_err());
+    assert!(parse_duration("ms").is_err());
+    assert!(parse_duration("99999999999999999999999s").is_err());
+  }
+
+  #[test]
+  fn test_current_token() {
+    let token = CancellationToken::default();
+    token.cancel();
+    assert!(token.scope(|| CancellationToken::current().is_cancelled()));
+    assert!(!CancellationToken::current().is_cancelled());
+  }
+}
@@ -0,0 +1,198 @@
+//! Workspace configuration read from `aoc.toml`.
+//!
+//! ```toml
+//! ledger = "ledger.jsonl"
+//!
+//! [days.lobby]
+//! part = "both"
+//! input = "real"
+//!
+//! [days.lobby.inputs.real]
+//! path = "inputs/lobby.txt"
+//! part-one = "357"
+//! ```
+//!
+//! Paths are relative to the directory holding `aoc.toml`. Without a config
+//! entry, a day's input falls back to `inputs/<crate-name>.txt`, or
+//! `inputs/<crate-name>.<set>.txt` for a named input set.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::Part;
+use crate::expect::Expected;
+
+pub static CONFIG_FILE: &str = "aoc.toml";
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+  /// Ledger every run is recorded to, see [`crate::ledger`].
+  ledger: Option<PathBuf>,
+  #[serde(default)]
+  days:   HashMap<String, DayConfig>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DayConfig {
+  /// Part solved when `--part` is omitted.
+  pub part: Option<Part>,
+  /// Input set used when neither `--file-path` nor `--input` is given.
+  input:    Option<String>,
+  #[serde(default)]
+  inputs:   HashMap<String, InputConfig>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct InputConfig {
+  path:     PathBuf,
+  part_one: Option<String>,
+  part_two: Option<String>,
+}
+
+/// An input file together with the answers known for it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+  pub path:     PathBuf,
+  pub expected: Expected,
+}
+
+#[derive(Default, Debug)]
+pub struct Workspace {
+  root:   PathBuf,
+  config: Config,
+}
+
+impl Workspace {
+  /// Looks for `aoc.toml` in the current directory and its ancestors, falling
+  /// back to an empty config rooted at the current directory.
+  pub fn discover() -> Result<Self> {
+    let cwd = env::current_dir().with_context(|| "cannot read current directory.")?;
+    match cwd
+      .ancestors()
+      .map(|dir| dir.join(CONFIG_FILE))
+      .find(|path| path.is_file())
+    {
+      Some(path) => Workspace::load(&path),
+      None => Ok(Workspace {
+        root:   cwd,
+        config: Config::default(),
+      }),
+    }
+  }
+
+  pub fn load(path: &Path) -> Result<Self> {
+    let content = fs::read_to_string(path).with_context(|| format!("cannot read `{}`.", path.display()))?;
+    Ok(Workspace {
+      root:   path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+      config: toml::from_str(&content).with_context(|| format!("invalid config `{}`.", path.display()))?,
+    })
+  }
+
+  pub fn root(&self) -> &Path { &self.root }
+
+  pub fn ledger(&self) -> Option<PathBuf> { self.config.ledger.as_ref().map(|path| self.root.join(path)) }
+
+  pub fn day(&self, name: &str) -> Option<&DayConfig> { self.config.days.get(name) }
+
+  /// Resolves the input of day `name`, either the named `set` or the day's
+  /// default one. Returns `None` when no set was asked for and the default
+  /// input file does not exist, so the caller can fall back to stdin.
+  pub fn resolve_input(&self, name: &str, set: Option<&str>) -> Result<Option<Input>> {
+    let day = self.day(name);
+    let explicit = set.is_some();
+    let set = set.or_else(|| day.and_then(|day| day.input.as_deref()));
+
+    let input = match set.and_then(|set| day?.inputs.get(set)) {
+      Some(input) => Input {
+        path:     self.root.join(&input.path),
+        expected: Expected::from_answers(input.part_one.clone(), input.part_two.clone()),
+      },
+      None => Input {
+        path:     match set {
+          Some(set) => self.root.join("inputs").join(format!("{name}.{set}.txt")),
+          None => self.root.join("inputs").join(format!("{name}.txt")),
+        },
+        expected: Expected::default(),
+      },
+    };
+
+    match (input.path.is_file(), explicit) {
+      (true, _) => Ok(Some(input)),
+      (false, true) => bail!(
+        "input set `{}` of `{name}` not found at `{}`.",
+        set.unwrap_or_default(),
+        input.path.display()
+      ),
+      (false, false) => Ok(None),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn workspace(config: &str) -> Workspace {
+    Workspace {
+      root:   PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+      config: toml::from_str(config).unwrap(),
+    }
+  }
+
+  #[test]
+  fn test_resolve_configured_input() {
+    let ws = workspace(
+      r#"
+      [days.lobby]
+      part = "second"
+      input = "real"
+
+      [days.lobby.inputs.real]
+      path = "Cargo.toml"
+
+      [days.lobby.inputs.example]
+      path = "src/lib.rs"
+      part-one = "357"
+
+      [days.lobby.inputs.missing]
+      path = "inputs/lobby.txt"
+      "#,
+    );
+
+    assert_eq!(ws.day("lobby").unwrap().part, Some(Part::Second));
+    assert_eq!(
+      ws.resolve_input("lobby", None).unwrap(),
+      Some(Input {
+        path:     ws.root().join("Cargo.toml"),
+        expected: Expected::default(),
+      })
+    );
+    assert_eq!(
+      ws.resolve_input("lobby", Some("example")).unwrap(),
+      Some(Input {
+        path:     ws.root().join("src/lib.rs"),
+        expected: Expected::from_answers(Some("357".to_string()), None),
+      })
+    );
+    assert!(ws.resolve_input("lobby", Some("missing")).is_err());
+  }
+
+  #[test]
+  fn test_resolve_missing_input() {
+    let ws = workspace("[days.lobby]\ninput = \"real\"");
+    assert_eq!(ws.resolve_input("lobby", None).unwrap(), None);
+    assert!(ws.resolve_input("lobby", Some("example")).is_err());
+  }
+
+  #[test]
+  fn test_unknown_keys_are_rejected() {
+    assert!(toml::from_str::<Config>("[days.lobby]\nanswer = 1").is_err());
+  }
+}
@@ -0,0 +1,141 @@
+//! Runs the worked example from a day's README through its [`App`].
+//!
+//! The example input is the first fenced code block of the README, and the
+//! stated answer of each part is the last bold number (`**357**`) in its `##
+//! Part One` / `## Part Two` section.
+
+use anyhow::{Context, Result, bail, ensure};
+
+use crate::expect::Expected;
+use crate::{App, Part, solve};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+  pub input:    String,
+  pub part_one: Option<String>,
+  pub part_two: Option<String>,
+}
+
+impl Example {
+  pub fn parse(readme: &str) -> Result<Self> {
+    let mut input: Option<String> = None;
+    let mut code_block: Option<String> = None;
+    let mut section = None;
+    let mut part_one = None;
+    let mut part_two = None;
+
+    for line in readme.lines() {
+      if line.starts_with("```") {
+        match code_block.take() {
+          Some(block) => {
+            input.get_or_insert(block);
+          }
+          None => code_block = Some(String::new()),
+        }
+        continue;
+      }
+      if let Some(block) = code_block.as_mut() {
+        block.push_str(line);
+        block.push('\n');
+        continue;
+      }
+
+      if let Some(heading) = line.strip_prefix("## ") {
+        section = match heading.trim() {
+          "Part One" | "Part 1" => Some(Part::First),
+          "Part Two" | "Part 2" => Some(Part::Second),
+          _ => None,
+        };
+        continue;
+      }
+
+      let Some(answer) = bold_numbers(line).last() else {
+        continue;
+      };
+      match section {
+        Some(Part::First) => part_one = Some(answer.to_string()),
+        Some(Part::Second) => part_two = Some(answer.to_string()),
+        _ => (),
+      }
+    }
+
+    ensure!(code_block.is_none(), "unterminated code block in README.");
+    Ok(Example {
+      input: input.context("no example input found in README.")?,
+      part_one,
+      part_two,
+    })
+  }
+}
+
+fn bold_numbers(line: &str) -> impl Iterator<Item = &str> {
+  line
+    .split("**")
+    .skip(1)
+    .step_by(2)
+    .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Solves every part the README states an answer for with each of its variants
+/// and fails on the first mismatch.
+pub fn check_readme<A: App>(app: A, readme: &str) -> Result<()> {
+  let example = Example::parse(readme)?;
+  ensure!(
+    example.part_one.is_some() || example.part_two.is_some(),
+    "no example answers found in README."
+  );
+  let expected = Expected::from_answers(example.part_one, example.part_two);
+
+  for &part in Part::Both.parts() {
+    for variant in A::variants(part) {
+      let report = solve(
+        app,
+        example.input.as_bytes(),
+        "README.md",
+        part,
+        variant,
+        &A::Params::default(),
+        None,
+      );
+      if let Some(error) = report.error {
+        bail!("{}", error.message);
+      }
+      if let Some(mismatch) = report.answer.and_then(|answer| expected.check(part, &answer)) {
+        bail!("variant `{}`: {mismatch}", variant.name);
+      }
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  static README: &str = "# Day 0\n\n## Part One\n\nFor **example**:\n\n```\n1\n2\n```\n\nThe sum is **3**.\n\n## Part \
+                         Two\n\n```\nInitial state:\n```\n\nThe product is now **2**, not 3.\n";
+
+  #[test]
+  fn test_parse() {
+    assert_eq!(Example::parse(README).unwrap(), Example {
+      input:    "1\n2\n".to_string(),
+      part_one: Some("3".to_string()),
+      part_two: Some("2".to_string()),
+    });
+  }
+
+  #[test]
+  fn test_parse_without_input() {
+    assert!(Example::parse("## Part One\n\nThe sum is **3**.").is_err());
+    assert!(Example::parse("## Part One\n\n```\n1\n").is_err());
+  }
+
+  #[test]
+  fn test_bold_numbers() {
+    assert_eq!(bold_numbers("a **b** c **12** d **3**").collect::<Vec<_>>(), vec![
+      "12", "3"
+    ]);
+    assert_eq!(bold_numbers("a **b** c 12").count(), 0);
+  }
+}
@@ -0,0 +1,137 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+
+use crate::Part;
+
+/// Known answers to compare the solver output against.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct Expected {
+  part_one: Option<String>,
+  part_two: Option<String>,
+}
+
+impl Expected {
+  /// Collects the answers from an answers file and then applies the `--expect`
+  /// values, in the same order the selected parts are solved.
+  pub fn new(part: Part, answers_path: Option<&Path>, expect: &[String]) -> Result<Self> {
+    let mut expected = match answers_path {
+      Some(path) => Expected::parse(&fs::read_to_string(path).with_context(|| "cannot read answers file.")?)?,
+      None => Expected::default(),
+    };
+
+    let parts = part.parts();
+    if expect.len() > parts.len() {
+      bail!("got {} expected answers for {} part(s).", expect.len(), parts.len());
+    }
+    for (&part, answer) in parts.iter().zip(expect) {
+      *expected.answer_mut(part) = Some(answer.clone());
+    }
+
+    Ok(expected)
+  }
+
+  pub fn from_answers(part_one: Option<String>, part_two: Option<String>) -> Self { Expected { part_one, part_two } }
+
+  /// Fills the answers missing from `self` with the ones from `fallback`.
+  pub fn or(self, fallback: Expected) -> Self {
+    Expected {
+      part_one: self.part_one.or(fallback.part_one),
+      part_two: self.part_two.or(fallback.part_two),
+    }
+  }
+
+  /// Parses the `Part one: <answer>` / `Part two: <answer>` lines printed by
+  /// `--part both`, so a previous run can be saved as the answers file.
+  fn parse(s: &str) -> Result<Self> {
+    let mut expected = Expected::default();
+    for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+      if let Some(answer) = line.strip_prefix("Part one:") {
+        expected.part_one = Some(answer.trim().to_string());
+      } else if let Some(answer) = line.strip_prefix("Part two:") {
+        expected.part_two = Some(answer.trim().to_string());
+      } else {
+        bail!("unexpected line in answers file: `{line}`");
+      }
+    }
+    Ok(expected)
+  }
+
+  fn answer_mut(&mut self, part: Part) -> &mut Option<String> {
+    match part {
+      Part::First => &mut self.part_one,
+      Part::Second => &mut self.part_two,
+      Part::Both => unreachable!("answers are stored per part"),
+    }
+  }
+
+  pub fn answer(&self, part: Part) -> Option<&str> {
+    match part {
+      Part::First => self.part_one.as_deref(),
+      Part::Second => self.part_two.as_deref(),
+      Part::Both => unreachable!("answers are checked per part"),
+    }
+  }
+
+  pub fn check(&self, part: Part, actual: &str) -> Option<Mismatch> {
+    let expected = self.answer(part)?;
+
+    (expected != actual).then(|| Mismatch {
+      part,
+      expected: expected.to_string(),
+      actual: actual.to_string(),
+    })
+  }
+}
+
+#[derive(Debug)]
+pub struct Mismatch {
+  part:     Part,
+  expected: String,
+  actual:   String,
+}
+
+impl Display for Mismatch {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "answer mismatch for part {}:", self.part.name())?;
+    writeln!(f, "- expected: {}", self.expected)?;
+    write!(f, "+ actual:   {}", self.actual)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let expected = Expected::parse("Part one: 3\n\nPart two: 6\n").unwrap();
+    assert_eq!(expected, Expected {
+      part_one: Some("3".to_string()),
+      part_two: Some("6".to_string()),
+    });
+
+    assert!(Expected::parse("Result: 3").is_err());
+  }
+
+  #[test]
+  fn test_expect_follows_selected_parts() {
+    let expected = Expected::new(Part::Second, None, &["6".to_string()]).unwrap();
+    assert_eq!(expected, Expected {
+      part_one: None,
+      part_two: Some("6".to_string()),
+    });
+
+    assert!(Expected::new(Part::First, None, &["3".to_string(), "6".to_string()]).is_err());
+  }
+
+  #[test]
+  fn test_check() {
+    let expected = Expected::new(Part::Both, None, &["3".to_string()]).unwrap();
+    assert!(expected.check(Part::First, "3").is_none());
+    assert!(expected.check(Part::First, "4").is_some());
+    assert!(expected.check(Part::Second, "6").is_none());
+  }
+}
@@ -0,0 +1,108 @@
+//! `aoc gen`: writes large random inputs of a day through its
+//! [`InputGenerator`], to stress-test and benchmark the solvers.
+//!
+//! The generator is seeded, so the same `--seed` always writes the same input
+//! and a slow or failing input can be shared by its seed alone.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, bail};
+
+use crate::{GenArgs, InputGenerator, Params, Part, cross_check, solve};
+
+/// SplitMix64 generator: small, fast and stable across releases, which seeded
+/// inputs need and statistical quality does not.
+pub struct Rng(u64);
+
+impl Rng {
+  pub fn new(seed: u64) -> Self { Rng(seed) }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+  }
+
+  /// Number in `range`, with a bias too small to matter for inputs.
+  pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+    let (start, end) = range.into_inner();
+    let span = (end - start).wrapping_add(1);
+    if span == 0 {
+      // The range covers every `u64`.
+      return self.next_u64();
+    }
+    start + ((self.next_u64() as u128 * span as u128) >> 64) as u64
+  }
+
+  /// `true` with probability `p`.
+  pub fn chance(&mut self, p: f64) -> bool { ((self.next_u64() >> 11) as f64) < p * (1_u64 << 53) as f64 }
+}
+
+/// Generates the input described by `args` and writes it to the output file or
+/// stdout. Without `--seed`, a random seed is picked and printed to stderr.
+pub fn run<A: InputGenerator>(app: A, args: GenArgs) -> Result<()> {
+  let shape = A::Shape::parse(&args.params)?;
+  let seed = args.seed.unwrap_or_else(|| {
+    let seed = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+    eprintln!("seed: {seed}");
+    seed
+  });
+
+  let mut out: Box<dyn Write> = match &args.output {
+    Some(path) => Box::new(BufWriter::new(
+      File::create(path).with_context(|| format!("cannot create `{}`.", path.display()))?,
+    )),
+    None => Box::new(BufWriter::new(io::stdout().lock())),
+  };
+  app
+    .generate(args.size, &shape, &mut Rng::new(seed), &mut out)
+    .and_then(|()| out.flush())
+    .context("cannot write the generated input.")
+}
+
+/// Generates an input of `size` with the default shape and solves both parts
+/// with every variant, failing if the input does not parse, a variant fails or
+/// the variants disagree.
+pub fn check<A: InputGenerator>(app: A, size: usize, seed: u64) -> Result<()> {
+  let mut input = Vec::new();
+  app.generate(size, &A::Shape::default(), &mut Rng::new(seed), &mut input)?;
+
+  for &part in Part::Both.parts() {
+    let reports = A::variants(part)
+      .into_iter()
+      .map(|variant| solve(app, &input, "generated", part, variant, &A::Params::default(), None))
+      .collect();
+    if let Some(error) = cross_check(reports).error {
+      bail!("seed {seed}: {}", error.message);
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_rng() {
+    let sample = |seed| {
+      let mut rng = Rng::new(seed);
+      (0..100).map(|_| rng.range(3..=7)).collect::<Vec<_>>()
+    };
+
+    assert_eq!(sample(42), sample(42));
+    assert_ne!(sample(42), sample(43));
+    assert!(sample(42).iter().all(|n| (3..=7).contains(n)));
+    assert!((3..=7).all(|n| sample(42).contains(&n)));
+    assert_eq!(Rng::new(0).range(5..=5), 5);
+    assert_eq!(Rng::new(42).range(0..=u64::MAX), Rng::new(42).next_u64());
+  }
+}
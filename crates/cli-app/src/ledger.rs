@@ -0,0 +1,229 @@
+//! Append-only JSON lines record of every answer, to catch regressions.
+//!
+//! Recording is opt-in, either with `--ledger <FILE>` or a `ledger` path in
+//! `aoc.toml`. Entries are keyed by day, part, variant, parameters, build
+//! profile and a hash of the input, so answers of different inputs are never
+//! compared, nor debug timings with release ones.
+
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::Report;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+  /// Seconds since the Unix epoch.
+  pub timestamp:  u64,
+  /// `git describe` of the workspace, when it is a git checkout.
+  pub revision:   Option<String>,
+  pub day:        u8,
+  pub part:       String,
+  pub variant:    String,
+  pub input_hash: String,
+  /// `debug` or `release`, as timings of both builds are worlds apart.
+  #[serde(default)]
+  pub profile:    String,
+  /// `--param` overrides the answer was computed with, sorted.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub params:     Vec<String>,
+  pub answer:     String,
+  pub parse_ns:   Option<u64>,
+  pub solve_ns:   Option<u64>,
+}
+
+impl Entry {
+  /// Records the answer of `report`, if it has one.
+  pub fn new(report: &Report, input_hash: &str, params: &[String], revision: Option<&str>) -> Option<Self> {
+    Some(Entry {
+      timestamp:  SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs()),
+      revision:   revision.map(str::to_string),
+      day:        report.day,
+      part:       report.part.to_string(),
+      variant:    report.variant.to_string(),
+      input_hash: input_hash.to_string(),
+      profile:    profile().to_string(),
+      params:     params.to_vec(),
+      answer:     report.answer.clone()?,
+      parse_ns:   report.parse_ns,
+      solve_ns:   report.solve_ns,
+    })
+  }
+
+  fn same_run(&self, other: &Entry) -> bool {
+    (
+      self.day,
+      &self.part,
+      &self.variant,
+      &self.input_hash,
+      &self.profile,
+      &self.params,
+    ) == (
+      other.day,
+      &other.part,
+      &other.variant,
+      &other.input_hash,
+      &other.profile,
+      &other.params,
+    )
+  }
+}
+
+fn profile() -> &'static str { if cfg!(debug_assertions) { "debug" } else { "release" } }
+
+pub struct Ledger {
+  path:    PathBuf,
+  entries: Vec<Entry>,
+}
+
+impl Ledger {
+  /// Reads the ledger at `path`, which does not need to exist yet.
+  pub fn open(path: &Path) -> Result<Self> {
+    let content = match fs::read_to_string(path) {
+      Ok(content) => content,
+      Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+      Err(err) => return Err(err).with_context(|| format!("cannot read ledger `{}`.", path.display())),
+    };
+    let entries = content
+      .lines()
+      .enumerate()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .map(|(i, line)| {
+        serde_json::from_str(line).with_context(|| format!("invalid entry at `{}:{}`.", path.display(), i + 1))
+      })
+      .collect::<Result<_>>()?;
+
+    Ok(Ledger {
+      path: path.to_path_buf(),
+      entries,
+    })
+  }
+
+  /// Compares `entry` with the earlier runs on the same input: the answer must
+  /// be the latest one recorded, and solving must not be more than `threshold`
+  /// percent slower than the fastest run.
+  pub fn check(&self, entry: &Entry, threshold: f64) -> Option<String> {
+    let previous = self.entries.iter().filter(|previous| previous.same_run(entry));
+
+    if let Some(last) = previous.clone().next_back()
+      && last.answer != entry.answer
+    {
+      return Some(format!(
+        "answer of part {} changed since {}:\n- recorded: {}\n+ actual:   {}",
+        entry.part,
+        last.revision.as_deref().unwrap_or("the last run"),
+        last.answer,
+        entry.answer
+      ));
+    }
+
+    let best = previous.filter_map(|previous| previous.solve_ns).min()?;
+    let solve_ns = entry.solve_ns?;
+    let slowdown = (solve_ns as f64 / best.max(1) as f64 - 1.) * 100.;
+    (slowdown > threshold).then(|| {
+      format!(
+        "part {} solved in {:.2?}, {slowdown:.0}% slower than the best recorded {:.2?}.",
+        entry.part,
+        Duration::from_nanos(solve_ns),
+        Duration::from_nanos(best)
+      )
+    })
+  }
+
+  pub fn append(&mut self, entries: Vec<Entry>) -> Result<()> {
+    let context = || format!("cannot write ledger `{}`.", self.path.display());
+    let mut file = OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&self.path)
+      .with_context(context)?;
+    for entry in &entries {
+      writeln!(file, "{}", serde_json::to_string(entry)?).with_context(context)?;
+    }
+    self.entries.extend(entries);
+    Ok(())
+  }
+}
+
+/// 64-bit FNV-1a hash of the input, which is stable across builds unlike the
+/// standard library's hasher.
+pub fn input_hash(input: &[u8]) -> String {
+  let hash = input.iter().fold(0xcbf29ce484222325_u64, |hash, &byte| {
+    (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+  });
+  format!("{hash:016x}")
+}
+
+/// `git describe` of the checkout at `root`, if git is available.
+pub fn revision(root: &Path) -> Option<String> {
+  let output = Command::new("git")
+    .args(["describe", "--always", "--dirty"])
+    .current_dir(root)
+    .output()
+    .ok()?;
+  let revision = String::from_utf8(output.stdout).ok()?;
+  output.status.success().then(|| revision.trim().to_string())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn entry(answer: &str, solve_ns: u64) -> Entry {
+    Entry {
+      timestamp:  0,
+      revision:   Some("abc1234".to_string()),
+      day:        3,
+      part:       "one".to_string(),
+      variant:    "default".to_string(),
+      input_hash: input_hash(b"987654321111111\n"),
+      profile:    "release".to_string(),
+      params:     Vec::new(),
+      answer:     answer.to_string(),
+      parse_ns:   None,
+      solve_ns:   Some(solve_ns),
+    }
+  }
+
+  #[test]
+  fn test_input_hash() {
+    assert_eq!(input_hash(b""), "cbf29ce484222325");
+    assert_eq!(input_hash(b"a"), "af63dc4c8601ec8c");
+  }
+
+  #[test]
+  fn test_check() {
+    let ledger = Ledger {
+      path:    PathBuf::new(),
+      entries: vec![entry("98", 1_000), entry("98", 800)],
+    };
+
+    assert_eq!(ledger.check(&entry("98", 1_000), 50.), None);
+    assert!(
+      ledger
+        .check(&entry("99", 800), 50.)
+        .unwrap()
+        .contains("changed since abc1234")
+    );
+    assert!(ledger.check(&entry("98", 1_600), 50.).unwrap().contains("100% slower"));
+
+    let other_input = Entry {
+      input_hash: input_hash(b"811111111111119\n"),
+      ..entry("12", 1_000_000)
+    };
+    assert_eq!(ledger.check(&other_input, 50.), None);
+
+    let debug_build = Entry {
+      profile: "debug".to_string(),
+      ..entry("98", 20_000)
+    };
+    assert_eq!(ledger.check(&debug_build, 50.), None);
+  }
+}
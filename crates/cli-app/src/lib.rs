@@ -1,45 +1,678 @@
+mod bench;
+mod cancel;
+pub mod config;
+pub mod examples;
+mod expect;
+pub mod generate;
+pub mod ledger;
+mod memory;
+pub mod params;
+mod parse;
+mod registry;
+mod report;
+mod summary;
+#[cfg(test)]
+mod test_day;
+mod variant;
+mod verbose;
+mod watch;
+
+use std::any::Any;
 use std::fmt::Display;
 use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::io::{self, BufRead, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use anyhow::{Context, Ok, Result};
+use anyhow::{Context, Result, anyhow, bail};
+pub use cancel::CancellationToken;
+use cancel::Phase;
 use clap::{Parser, ValueEnum};
+pub use expect::{Expected, Mismatch};
+pub use generate::Rng;
+pub use memory::Allocations;
+pub use params::Params;
+pub use parse::{Line, ParseError, lines};
+pub use registry::{Generator, Registry, Solver};
+use report::{FailureKind, Printer};
+pub use report::{Format, Report};
+use serde::Deserialize;
+pub use summary::run_all;
+use tracing::info_span;
+pub use variant::Variant;
+
+/// A single day's puzzle solver.
+///
+/// Solving consumes the parsed input, so `parse_input` is called once for every
+/// part being solved. With `--timeout`, both run on a worker thread, and
+/// `aoc all` solves the days in parallel.
+pub trait App: Copy + Send + Sync + 'static {
+  /// Day of the advent calendar the puzzle was published on.
+  const DAY: u8;
+  /// Crate name of the day, used to select it in the `aoc` runner.
+  const NAME: &'static str;
 
-pub trait App: Copy {
   type Input;
   type Output: Display;
+  /// Puzzle constants set with `--param key=value`, `()` for days without any.
+  type Params: Params;
+
+  fn parse_input(self, buf: impl BufRead) -> Result<Self::Input, ParseError>;
+  fn solve_part_one(self, input: Self::Input, params: &Self::Params) -> Result<Self::Output>;
+  fn solve_part_two(self, input: Self::Input, params: &Self::Params) -> Result<Self::Output>;
 
-  fn parse_input(self, buf: BufReader<File>) -> Result<Self::Input>;
-  fn solve_part_one(self, input: Self::Input) -> Result<Self::Output>;
-  fn solve_part_two(self, input: Self::Input) -> Result<Self::Output>;
+  /// Solvers available for `part`, the first one being the default. Days with
+  /// alternative implementations override this to register them by name.
+  fn variants(part: Part) -> Vec<Variant<Self>> {
+    match part {
+      Part::First => vec![Variant::new("default", Self::solve_part_one)],
+      Part::Second => vec![Variant::new("default", Self::solve_part_two)],
+      Part::Both => Vec::new(),
+    }
+  }
+}
+
+/// Random inputs of a day, written by `aoc gen` to stress-test and benchmark
+/// its solvers.
+pub trait InputGenerator: App {
+  /// Knobs of the generated input besides its size, set with `--param
+  /// key=value`, e.g. the density of a grid.
+  type Shape: Params;
+
+  /// Writes a valid input of `size` items, e.g. lines or rows, drawing every
+  /// random choice from `rng`.
+  fn generate(self, size: usize, shape: &Self::Shape, rng: &mut Rng, out: &mut dyn Write) -> io::Result<()>;
 }
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
-struct Args {
-  #[arg(short, long)]
-  part:      Part,
+struct Cli {
+  #[command(flatten)]
+  args: RunArgs,
+}
+
+/// Options selecting the input of a day and its puzzle constants.
+#[derive(clap::Args, Debug)]
+pub struct InputArgs {
+  /// Input file, `-` reads stdin. When omitted, the input is looked up in
+  /// `aoc.toml` and `inputs/<crate-name>.txt` before falling back to stdin.
   #[arg(short, long)]
-  file_path: PathBuf,
+  file_path: Option<PathBuf>,
+  /// Named input set from `aoc.toml`, e.g. `example` or `real`.
+  #[arg(short, long, value_name = "SET", conflicts_with = "file_path")]
+  input:     Option<String>,
+  /// Override a puzzle constant of the day, e.g. `--param batteries=3`.
+  #[arg(long = "param", value_name = "KEY=VALUE")]
+  params:    Vec<String>,
+}
+
+impl InputArgs {
+  /// Input file selected for the day `name`, `None` for stdin, along with the
+  /// input set it comes from when it is configured in `aoc.toml`.
+  fn resolve(&self, workspace: &config::Workspace, name: &str) -> Result<(Option<PathBuf>, Option<config::Input>)> {
+    let configured = match &self.file_path {
+      Some(_) => None,
+      None => workspace.resolve_input(name, self.input.as_deref())?,
+    };
+    let file_path = self
+      .file_path
+      .clone()
+      .or_else(|| configured.as_ref().map(|input| input.path.clone()));
+    Ok((file_path, configured))
+  }
+
+  /// Reads and parses the selected input of `app` along with its parameters,
+  /// for the subcommands a day adds to its own binary.
+  pub fn load<A: App>(&self, app: A) -> Result<(A::Input, A::Params)> {
+    self.load_with::<A, _>(|input| app.parse_input(input))
+  }
+
+  /// Like [`InputArgs::load`], for subcommands reading an input format of
+  /// their own with `parse`.
+  pub fn load_with<A: App, T>(&self, parse: impl FnOnce(&[u8]) -> Result<T, ParseError>) -> Result<(T, A::Params)> {
+    let (file_path, _) = self.resolve(&config::Workspace::discover()?, A::NAME)?;
+    let input = read_input(file_path.as_deref())?;
+    let parsed = parse(&input).map_err(|err| match &file_path {
+      Some(path) if path != Path::new("-") => err.with_file(path),
+      _ => err,
+    })?;
+    Ok((parsed, A::Params::parse(&self.params)?))
+  }
+}
+
+/// Options shared by every day's binary and the `aoc run` subcommand.
+#[derive(clap::Args, Debug)]
+pub struct RunArgs {
+  /// Part to solve, defaults to the day's configured part or both.
+  #[arg(short, long, value_enum)]
+  part:                 Option<Part>,
+  #[command(flatten)]
+  source:               InputArgs,
+  /// Time parsing and solving over N iterations instead of printing the
+  /// answers.
+  #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+  bench:                Option<u32>,
+  /// Expected answer, given once per solved part; exits with an error on a
+  /// mismatch.
+  #[arg(long, value_name = "ANSWER")]
+  expect:               Vec<String>,
+  /// File with `Part one: <answer>` / `Part two: <answer>` lines to check the
+  /// answers against.
+  #[arg(long, value_name = "FILE")]
+  answers:              Option<PathBuf>,
+  #[arg(long, value_enum, default_value_t = Format::Text)]
+  format:               Format,
+  /// Named solver variant to run instead of the default one, for the parts
+  /// that have it.
+  #[arg(long, value_name = "NAME")]
+  variant:              Option<String>,
+  /// Run every variant of the selected parts and fail if their answers differ.
+  #[arg(long, conflicts_with = "variant")]
+  cross_check:          bool,
+  /// Solve again every time the input file changes, printing how the answers
+  /// moved.
+  #[arg(long, conflicts_with = "bench")]
+  watch:                bool,
+  /// Give up on a part that takes longer than this to parse and solve, e.g.
+  /// `500ms`, `30s` or `2m`.
+  #[arg(long, value_name = "DURATION", value_parser = cancel::parse_duration)]
+  timeout:              Option<Duration>,
+  /// Record the answers to this ledger, instead of the one set in `aoc.toml`.
+  #[arg(long, value_name = "FILE")]
+  ledger:               Option<PathBuf>,
+  /// Fail when an answer differs from the one last recorded in the ledger for
+  /// the same input, or solving got slower than the threshold.
+  #[arg(long)]
+  check_ledger:         bool,
+  /// Slowdown over the fastest recorded run tolerated by `--check-ledger`.
+  #[arg(long, value_name = "PERCENT", default_value_t = 50.)]
+  regression_threshold: f64,
+  /// Record the answers and timings `--check-ledger` flags anyway, making them
+  /// the reference of the next checks.
+  #[arg(long, requires = "check_ledger")]
+  accept:               bool,
+  /// Log the steps of the solvers to stderr, `-vv` for every single one.
+  #[arg(short, long, action = clap::ArgAction::Count)]
+  verbose:              u8,
+}
+
+/// Options of the `aoc all` subcommand.
+#[derive(clap::Args, Debug)]
+pub struct AllArgs {
+  /// Named input set from `aoc.toml` to solve instead of each day's default.
+  #[arg(short, long, value_name = "SET")]
+  input:   Option<String>,
+  /// Give up on a part that takes longer than this to parse and solve.
+  #[arg(long, value_name = "DURATION", value_parser = cancel::parse_duration)]
+  timeout: Option<Duration>,
 }
 
-#[derive(ValueEnum, Clone, Debug)]
-enum Part {
+/// Options of the `aoc gen` subcommand.
+#[derive(clap::Args, Debug)]
+pub struct GenArgs {
+  /// Number of items to generate, e.g. rotations, ranges, banks or rows.
+  #[arg(long, default_value_t = 1000)]
+  size:   usize,
+  /// Seed of the generator, the same seed always generates the same input.
+  /// Picked at random and printed to stderr when omitted.
+  #[arg(long)]
+  seed:   Option<u64>,
+  /// Shape of the input, e.g. `--param density=0.3` for a sparser grid.
+  #[arg(long = "param", value_name = "KEY=VALUE")]
+  params: Vec<String>,
+  /// File to write the input to, instead of stdout.
+  #[arg(short, long, value_name = "FILE")]
+  output: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Part {
   First,
   Second,
+  Both,
 }
 
-pub fn run(app: impl App) -> Result<()> {
-  let args = Args::parse();
-  let file = File::open(args.file_path).with_context(|| "cannot open file.")?;
+impl Part {
+  fn parts(self) -> &'static [Part] {
+    match self {
+      Part::First => &[Part::First],
+      Part::Second => &[Part::Second],
+      Part::Both => &[Part::First, Part::Second],
+    }
+  }
+
+  fn name(self) -> &'static str {
+    match self {
+      Part::First => "one",
+      Part::Second => "two",
+      Part::Both => "both",
+    }
+  }
+}
 
-  let input = app.parse_input(BufReader::new(file))?;
+pub fn run(app: impl App) -> Result<()> { run_with(app, Cli::parse().args) }
+
+pub fn run_with<A: App>(app: A, args: RunArgs) -> Result<()> {
+  verbose::init(args.verbose);
+  let format = args.format;
+  let part = args.part.map_or("-", Part::name);
+  let input_name = args.source.file_path.as_deref().map_or_else(
+    || args.source.input.clone().unwrap_or_else(|| "-".to_string()),
+    |path| path.display().to_string(),
+  );
+
+  solve_run(app, args).or_else(|err| {
+    let setup = err.downcast::<SetupError>()?;
+    if !matches!(format, Format::Text) {
+      let mut report = Report::new(A::DAY, part, "-", &input_name);
+      report.fail(setup.kind, format_args!("{:#}", setup.error));
+      Printer::new(format, false).print(&report)?;
+    }
+    Err(setup.error)
+  })
+}
+
+/// A failure before any part could be solved, such as an unreadable input or
+/// `aoc.toml`, which the structured formats emit as a record too.
+#[derive(Debug)]
+struct SetupError {
+  kind:  FailureKind,
+  error: anyhow::Error,
+}
 
-  match args.part {
-    Part::First => println!("Result: {}", app.solve_part_one(input)?),
-    Part::Second => println!("Result: {}", app.solve_part_two(input)?),
+impl Display for SetupError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{:#}", self.error) }
+}
+
+impl std::error::Error for SetupError {}
+
+fn setup<T>(kind: FailureKind, result: Result<T>) -> Result<T> {
+  result.map_err(|error| SetupError { kind, error }.into())
+}
+
+fn solve_run<A: App>(app: A, args: RunArgs) -> Result<()> {
+  let workspace = setup(FailureKind::Config, config::Workspace::discover())?;
+  let part = args.part.or_else(|| workspace.day(A::NAME)?.part).unwrap_or(Part::Both);
+  let (file_path, configured) = setup(FailureKind::Config, args.source.resolve(&workspace, A::NAME))?;
+  let plan = setup(
+    FailureKind::Config,
+    Variant::select(part.parts(), args.variant.as_deref(), args.cross_check),
+  )?;
+  let params = setup(FailureKind::Config, A::Params::parse(&args.source.params))?;
+
+  if let Some(iterations) = args.bench {
+    let input = setup(FailureKind::Io, read_input(file_path.as_deref()))?;
+    return bench::run(app, &input, &params, &plan, iterations);
+  }
+
+  // The answers in `aoc.toml` only hold for the puzzle's own parameters.
+  let configured = configured.filter(|_| args.source.params.is_empty());
+  let expected = setup(
+    FailureKind::Config,
+    expect::Expected::new(part, args.answers.as_deref(), &args.expect),
+  )?
+  .or(configured.map(|input| input.expected).unwrap_or_default());
+  let input_name = file_path.as_deref().unwrap_or(Path::new("-")).display().to_string();
+  let solve_plan = |input: &[u8]| {
+    plan
+      .iter()
+      .map(|(part, variants)| {
+        let reports = variants
+          .iter()
+          .map(|&variant| solve(app, input, &input_name, *part, variant, &params, args.timeout))
+          .collect();
+        let mut report = cross_check(reports);
+        report.check(*part, &expected);
+        report
+      })
+      .collect::<Vec<_>>()
+  };
+  let mut printer = Printer::new(args.format, part == Part::Both);
+
+  if args.watch {
+    match file_path.as_deref().filter(|&path| path != Path::new("-")) {
+      Some(path) => return watch::run(path, &mut printer, solve_plan),
+      None => return setup(FailureKind::Config, Err(anyhow!("--watch needs an input file."))),
+    }
+  }
+
+  let input = setup(FailureKind::Io, read_input(file_path.as_deref()))?;
+  let mut reports = solve_plan(&input);
+  if let Some(path) = args.ledger.or_else(|| workspace.ledger()) {
+    let mut ledger = setup(FailureKind::Io, ledger::Ledger::open(&path))?;
+    let input_hash = ledger::input_hash(&input);
+    let revision = ledger::revision(workspace.root());
+    let mut params = args.source.params.clone();
+    params.sort();
+    let mut entries = Vec::new();
+    for report in &mut reports {
+      let Some(entry) = ledger::Entry::new(report, &input_hash, &params, revision.as_deref()) else {
+        continue;
+      };
+      if args.check_ledger
+        && let Some(regression) = ledger.check(&entry, args.regression_threshold)
+      {
+        report.fail(FailureKind::Regression, regression);
+        // Kept out of the ledger so the next check still compares against the
+        // runs before it, unless the change is accepted.
+        if !args.accept {
+          continue;
+        }
+      }
+      entries.push(entry);
+    }
+    setup(FailureKind::Io, ledger.append(entries))?;
+  } else if args.check_ledger {
+    return setup(
+      FailureKind::Config,
+      Err(anyhow!(
+        "--check-ledger needs a ledger, pass --ledger or set `ledger` in `{}`.",
+        config::CONFIG_FILE
+      )),
+    );
+  }
+
+  let mut failures = Vec::new();
+  for report in reports {
+    printer.print(&report)?;
+    // Every part parses the input again, failing the same way on a malformed one.
+    if let Some(error) = report.error
+      && !failures.contains(&error.message)
+    {
+      failures.push(error.message);
+    }
+  }
+
+  if !failures.is_empty() {
+    bail!("{}", failures.join("\n"));
   }
 
   Ok(())
 }
+
+/// Solves both parts of a day with their default variants against the day's
+/// configured input, or the input `set`, checking the answers known for it.
+fn solve_configured<A: App>(
+  app: A,
+  workspace: &config::Workspace,
+  set: Option<&str>,
+  timeout: Option<Duration>,
+) -> Result<Vec<Report>> {
+  let configured = workspace
+    .resolve_input(A::NAME, set)?
+    .with_context(|| format!("no input for `{}`, see `aoc.toml`.", A::NAME))?;
+  let input = read_input(Some(&configured.path))?;
+  let input_name = configured.path.display().to_string();
+
+  let reports = Variant::select(Part::Both.parts(), None, false)?
+    .into_iter()
+    .map(|(part, variants)| {
+      let reports = variants
+        .into_iter()
+        .map(|variant| solve(app, &input, &input_name, part, variant, &A::Params::default(), timeout))
+        .collect();
+      let mut report = cross_check(reports);
+      report.check(part, &configured.expected);
+      report
+    })
+    .collect();
+  Ok(reports)
+}
+
+/// Parses and solves a single part, timing both phases and recording a failure,
+/// panics included, in the report instead of returning it. Past `timeout`, the
+/// solver is left to its worker thread with its [`CancellationToken`]
+/// cancelled.
+fn solve<A: App>(
+  app: A,
+  input: &[u8],
+  input_name: &str,
+  part: Part,
+  variant: Variant<A>,
+  params: &A::Params,
+  timeout: Option<Duration>,
+) -> Report {
+  let report = Report::new(A::DAY, part.name(), variant.name, input_name);
+  let Some(timeout) = timeout else {
+    let mut last = report.clone();
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+      solve_phases(app, input, report, variant, params, |report| last = report.clone())
+    }));
+    return solved.unwrap_or_else(|payload| panicked(last, part, Some(payload.as_ref())));
+  };
+
+  enum Progress {
+    Parsed(Report),
+    Solved(Report),
+  }
+
+  let (sender, receiver) = mpsc::channel();
+  let token = CancellationToken::default();
+  let mut last = report.clone();
+  thread::spawn({
+    let input = input.to_vec();
+    let params = params.clone();
+    let token = token.clone();
+    move || {
+      let report = token.scope(|| {
+        solve_phases(app, &input, report, variant, &params, |report| {
+          let _ = sender.send(Progress::Parsed(report.clone()));
+        })
+      });
+      let _ = sender.send(Progress::Solved(report));
+    }
+  });
+
+  // A timeout too long to be represented is no timeout at all.
+  let deadline = Instant::now().checked_add(timeout);
+  let phase = loop {
+    let progress = match deadline {
+      Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+      None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match progress {
+      Ok(Progress::Parsed(report)) => last = report,
+      Ok(Progress::Solved(report)) => return report,
+      Err(RecvTimeoutError::Timeout) if last.parse_ns.is_some() => break Phase::Solve,
+      Err(RecvTimeoutError::Timeout) => break Phase::Parse,
+      Err(RecvTimeoutError::Disconnected) => return panicked(last, part, None),
+    }
+  };
+
+  token.cancel();
+  last.fail(
+    FailureKind::Timeout,
+    format_args!("part {} timed out {phase} after {timeout:?}", part.name()),
+  );
+  last
+}
+
+/// Fails the `last` report of a solver that panicked in the phase it reached,
+/// with the panic message when its `payload` is at hand.
+fn panicked(mut last: Report, part: Part, payload: Option<&(dyn Any + Send)>) -> Report {
+  let message = payload
+    .and_then(|payload| {
+      (payload.downcast_ref::<&str>().copied()).or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+    })
+    .map_or_else(|| "panicked".to_string(), |message| format!("panicked: {message}"));
+  if last.parse_ns.is_some() {
+    last.fail(
+      FailureKind::Solve,
+      format_args!("cannot solve part {}: {message}", part.name()),
+    );
+  } else {
+    last.fail(FailureKind::Parse, format_args!("cannot parse input: {message}"));
+  }
+  last
+}
+
+/// Runs both phases of [`solve`], handing the report to `on_parsed` once the
+/// input is parsed.
+fn solve_phases<A: App>(
+  app: A,
+  input: &[u8],
+  mut report: Report,
+  variant: Variant<A>,
+  params: &A::Params,
+  on_parsed: impl FnOnce(&Report),
+) -> Report {
+  let ((parsed, elapsed), allocations) =
+    info_span!("parse", day = A::DAY, part = report.part, variant = report.variant)
+      .in_scope(|| memory::measured(|| bench::timed(|| app.parse_input(input))));
+  report.parsed_in(elapsed);
+  report.parse_alloc = allocations;
+  let parsed = match parsed {
+    Ok(parsed) => parsed,
+    Err(err) => {
+      let err = if report.input == "-" {
+        err
+      } else {
+        err.with_file(&report.input)
+      };
+      report.fail(FailureKind::Parse, format_args!("cannot parse input: {err}"));
+      return report;
+    }
+  };
+  on_parsed(&report);
+
+  let ((solved, elapsed), allocations) =
+    info_span!("solve", day = A::DAY, part = report.part, variant = report.variant)
+      .in_scope(|| memory::measured(|| bench::timed(|| (variant.solve)(app, parsed, params))));
+  report.solved_in(elapsed);
+  report.solve_alloc = allocations;
+  match solved {
+    Ok(answer) => report.answer = Some(answer.to_string()),
+    Err(err) => {
+      let message = format!("cannot solve part {}: {err:#}", report.part);
+      report.fail(FailureKind::Solve, message)
+    }
+  }
+
+  report
+}
+
+/// Reduces the reports of every variant of a part to the first one, failing it
+/// when another variant failed or came up with a different answer.
+fn cross_check(mut reports: Vec<Report>) -> Report {
+  if let Some(at) = reports.iter().position(|report| report.error.is_some()) {
+    return reports.swap_remove(at);
+  }
+
+  let mut report = reports.swap_remove(0);
+  if reports.iter().any(|other| other.answer != report.answer) {
+    let answers = std::iter::once(&report)
+      .chain(&reports)
+      .map(|report| format!("  {}: {}", report.variant, report.answer.as_deref().unwrap_or_default()))
+      .collect::<Vec<_>>()
+      .join("\n");
+    let message = format!("variants disagree on part {}:\n{answers}", report.part);
+    report.fail(FailureKind::Disagreement, message);
+  }
+  report
+}
+
+/// Reads the whole input up front, so it can be parsed again for every part
+/// even when it comes from stdin.
+fn read_input(path: Option<&Path>) -> Result<Vec<u8>> {
+  let mut input = Vec::new();
+  match path.filter(|&path| path != Path::new("-")) {
+    Some(path) => File::open(path)
+      .and_then(|mut file| file.read_to_end(&mut input))
+      .with_context(|| format!("cannot open file `{}`.", path.display()))?,
+    None => io::stdin()
+      .lock()
+      .read_to_end(&mut input)
+      .with_context(|| "cannot read stdin.")?,
+  };
+  Ok(input)
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::atomic::Ordering;
+
+  use super::*;
+  use crate::test_day::{Day, SPIN_CANCELLED};
+
+  #[test]
+  fn test_solve_timeout() {
+    let timeout = Some(Duration::from_millis(20));
+    let report = solve(
+      Day::<2>,
+      b"spin",
+      "-",
+      Part::First,
+      Day::<2>::variants(Part::First)[0],
+      &(),
+      timeout,
+    );
+    assert!(report.parse_ns.is_some());
+    assert_eq!(report.error.unwrap().kind, FailureKind::Timeout);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !SPIN_CANCELLED.load(Ordering::Relaxed) && Instant::now() < deadline {
+      thread::yield_now();
+    }
+    assert!(SPIN_CANCELLED.load(Ordering::Relaxed));
+
+    let timeout = Some(Duration::from_secs(5));
+    let report = solve(
+      Day::<2>,
+      b"spin",
+      "-",
+      Part::Second,
+      Day::<2>::variants(Part::Second)[0],
+      &(),
+      timeout,
+    );
+    assert_eq!(report.answer.as_deref(), Some("2"));
+  }
+
+  #[test]
+  fn test_solve_panic() {
+    for timeout in [None, Some(Duration::from_secs(5))] {
+      let report = solve(
+        Day::<2>,
+        b"panic",
+        "-",
+        Part::First,
+        Day::<2>::variants(Part::First)[0],
+        &(),
+        timeout,
+      );
+      assert!(report.parse_ns.is_some());
+      let error = report.error.unwrap();
+      assert_eq!(error.kind, FailureKind::Solve);
+      assert!(error.message.starts_with("cannot solve part one: panicked"));
+
+      let report = solve(
+        Day::<2>,
+        b"unparsable",
+        "-",
+        Part::Second,
+        Day::<2>::variants(Part::Second)[0],
+        &(),
+        timeout,
+      );
+      assert_eq!(report.error.unwrap().kind, FailureKind::Parse);
+    }
+
+    let report = solve(
+      Day::<2>,
+      b"panic",
+      "-",
+      Part::First,
+      Day::<2>::variants(Part::First)[0],
+      &(),
+      None,
+    );
+    assert_eq!(
+      report.error.unwrap().message,
+      "cannot solve part one: panicked: overflow"
+    );
+  }
+}
@@ -0,0 +1,147 @@
+//! Allocation accounting, enabled by the `alloc-stats` feature.
+//!
+//! The feature installs a global allocator that counts the allocations of the
+//! current thread, so a measurement is not disturbed by other threads. This
+//! also leaves out the threads a solver spawns itself, such as the chunks of
+//! the secret entrance's `chunked` variant: only what the solver allocates on
+//! the thread it was called on is counted.
+
+use std::fmt::{self, Display};
+
+use serde::Serialize;
+
+/// Allocations made while running a single phase, on its own thread only.
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Allocations {
+  /// Number of allocations, reallocations included.
+  pub count: u64,
+  /// Total bytes requested.
+  pub bytes: u64,
+  /// Highest number of bytes live at once, on top of what was live before.
+  pub peak:  u64,
+}
+
+impl Display for Allocations {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} allocations, {} bytes, peak {} bytes",
+      self.count, self.bytes, self.peak
+    )
+  }
+}
+
+/// Runs `f`, returning the allocations it made, or `None` when the crate was
+/// built without the `alloc-stats` feature.
+#[cfg(not(feature = "alloc-stats"))]
+pub fn measured<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) { (f(), None) }
+
+#[cfg(feature = "alloc-stats")]
+pub use counting::measured;
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+  use std::alloc::{GlobalAlloc, Layout, System};
+  use std::cell::Cell;
+
+  use super::Allocations;
+
+  #[derive(Clone, Copy, Default)]
+  struct Counters {
+    count: u64,
+    bytes: u64,
+    live:  i64,
+    peak:  i64,
+  }
+
+  thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+      Cell::new(Counters {
+        count: 0,
+        bytes: 0,
+        live:  0,
+        peak:  0,
+      })
+    };
+  }
+
+  /// Records an allocation of `new` bytes replacing `old` ones.
+  fn record(old: usize, new: usize) {
+    // The thread local is gone while the thread is being torn down.
+    let _ = COUNTERS.try_with(|counters| {
+      let mut c = counters.get();
+      if new > 0 {
+        c.count += 1;
+        c.bytes += new as u64;
+      }
+      c.live += new as i64 - old as i64;
+      c.peak = c.peak.max(c.live);
+      counters.set(c);
+    });
+  }
+
+  struct Counting;
+
+  #[global_allocator]
+  static ALLOCATOR: Counting = Counting;
+
+  unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+      record(0, layout.size());
+      unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+      record(0, layout.size());
+      unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+      record(layout.size(), 0);
+      unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+      record(layout.size(), new_size);
+      unsafe { System.realloc(ptr, layout, new_size) }
+    }
+  }
+
+  pub fn measured<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    let outer = COUNTERS.replace(Counters::default());
+    let value = f();
+    let inner = COUNTERS.replace(Counters::default());
+    // Fold the measurement back in, so enclosing measurements still see it.
+    COUNTERS.set(Counters {
+      count: outer.count + inner.count,
+      bytes: outer.bytes + inner.bytes,
+      live:  outer.live + inner.live,
+      peak:  outer.peak.max(outer.live + inner.peak),
+    });
+
+    let allocations = Allocations {
+      count: inner.count,
+      bytes: inner.bytes,
+      peak:  inner.peak as u64,
+    };
+    (value, Some(allocations))
+  }
+
+  #[cfg(test)]
+  mod test {
+    use super::*;
+
+    #[test]
+    fn test_measured() {
+      let (buf, allocations) = measured(|| vec![0u8; 4096]);
+      let allocations = allocations.unwrap();
+      assert_eq!(buf.len(), 4096);
+      assert!(allocations.count >= 1);
+      assert!(allocations.bytes >= 4096);
+      assert!(allocations.peak >= 4096);
+
+      let (_, allocations) = measured(|| drop(buf));
+      assert_eq!(allocations.unwrap().peak, 0);
+    }
+  }
+}
@@ -0,0 +1,92 @@
+//! Puzzle constants a day lets the user override with `--param key=value`, to
+//! explore variants of the puzzle without recompiling.
+
+use std::error::Error;
+use std::str::FromStr;
+
+use anyhow::{Context, Result, bail};
+
+/// Puzzle constants of a day, defaulting to the values of the puzzle.
+pub trait Params: Default + Clone + Send + Sync + 'static {
+  /// Sets the parameter `key` from its command line `value`, failing on keys
+  /// the day does not know.
+  fn set(&mut self, key: &str, value: &str) -> Result<()>;
+
+  /// Checks the parameters make sense together, once they are all set.
+  fn validate(&self) -> Result<()> { Ok(()) }
+
+  /// Applies `key=value` pairs on top of the defaults.
+  fn parse(pairs: &[String]) -> Result<Self> {
+    let mut params = Self::default();
+    for pair in pairs {
+      let Some((key, value)) = pair.split_once('=') else {
+        bail!("invalid parameter `{pair}`, expected `key=value`.");
+      };
+      params.set(key.trim(), value.trim())?;
+    }
+    params.validate()?;
+    Ok(params)
+  }
+}
+
+impl Params for () {
+  fn set(&mut self, key: &str, _value: &str) -> Result<()> { bail!("unknown parameter `{key}`, this day has none.") }
+}
+
+/// Parses the `value` given to the parameter `key`.
+pub fn value<T>(key: &str, value: &str) -> Result<T>
+where
+  T: FromStr,
+  T::Err: Error + Send + Sync + 'static,
+{
+  value
+    .parse()
+    .with_context(|| format!("invalid value `{value}` for parameter `{key}`."))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[derive(Clone, Debug, PartialEq)]
+  struct Dial {
+    size:  usize,
+    start: usize,
+  }
+
+  impl Default for Dial {
+    fn default() -> Self { Dial { size: 100, start: 50 } }
+  }
+
+  impl Params for Dial {
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+      match key {
+        "size" => self.size = super::value(key, value)?,
+        "start" => self.start = super::value(key, value)?,
+        _ => bail!("unknown parameter `{key}`, expected `size` or `start`."),
+      }
+      Ok(())
+    }
+
+    fn validate(&self) -> Result<()> {
+      anyhow::ensure!(self.start < self.size, "the dial starts past its size.");
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn test_parse() {
+    let pairs = |pairs: &[&str]| pairs.iter().map(|pair| pair.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(Dial::parse(&[]).unwrap(), Dial::default());
+    assert_eq!(Dial::parse(&pairs(&["size=10", "start = 3"])).unwrap(), Dial {
+      size:  10,
+      start: 3,
+    });
+    assert!(Dial::parse(&pairs(&["size"])).is_err());
+    assert!(Dial::parse(&pairs(&["size=ten"])).is_err());
+    assert!(Dial::parse(&pairs(&["speed=1"])).is_err());
+    assert!(Dial::parse(&pairs(&["size=10", "start=10"])).is_err());
+    assert!(<()>::parse(&pairs(&["size=10"])).is_err());
+  }
+}
@@ -0,0 +1,124 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io::BufRead;
+use std::path::PathBuf;
+
+/// A malformed input, located precisely enough to print a compiler-style
+/// diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+  /// Input file, `None` for stdin or in-memory buffers.
+  pub file:     Option<PathBuf>,
+  /// 1-based line number.
+  pub line:     usize,
+  /// 1-based column, counted in characters.
+  pub column:   usize,
+  /// Whole source line the error points into.
+  pub snippet:  String,
+  /// Number of characters to underline, starting at `column`.
+  pub width:    usize,
+  /// Shape the parser was expecting, e.g. "a rotation like `L68`".
+  pub expected: String,
+}
+
+impl ParseError {
+  pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+    self.file = Some(file.into());
+    self
+  }
+}
+
+impl Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let file = self
+      .file
+      .as_ref()
+      .map_or("<stdin>".to_string(), |file| file.display().to_string());
+    let gutter = " ".repeat(self.line.to_string().len());
+
+    writeln!(f, "expected {}", self.expected)?;
+    writeln!(f, "{gutter}--> {file}:{}:{}", self.line, self.column)?;
+    writeln!(f, "{gutter} |")?;
+    writeln!(f, "{} | {}", self.line, self.snippet)?;
+    write!(
+      f,
+      "{gutter} | {}{}",
+      " ".repeat(self.column - 1),
+      "^".repeat(self.width.max(1))
+    )
+  }
+}
+
+impl Error for ParseError {}
+
+/// A numbered line of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+  /// 1-based line number.
+  pub number: usize,
+  pub text:   String,
+}
+
+impl Line {
+  /// Builds an error underlining `len` bytes of the line starting at the byte
+  /// offset `start`.
+  pub fn error(&self, start: usize, len: usize, expected: impl Into<String>) -> ParseError {
+    ParseError {
+      file:     None,
+      line:     self.number,
+      column:   self.text[..start].chars().count() + 1,
+      snippet:  self.text.clone(),
+      width:    self.text[start..].char_indices().take_while(|&(i, _)| i < len).count(),
+      expected: expected.into(),
+    }
+  }
+}
+
+/// Splits the input into numbered lines, reporting unreadable lines as parse
+/// errors.
+pub fn lines(buf: impl BufRead) -> impl Iterator<Item = Result<Line, ParseError>> {
+  buf.lines().enumerate().map(|(i, line)| {
+    let number = i + 1;
+    line.map(|text| Line { number, text }).map_err(|err| ParseError {
+      file:     None,
+      line:     number,
+      column:   1,
+      snippet:  String::new(),
+      width:    0,
+      expected: format!("readable UTF-8 text ({err})"),
+    })
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_display() {
+    let line = Line {
+      number: 12,
+      text:   "L68,R1x".to_string(),
+    };
+    let err = line.error(5, 2, "a rotation distance").with_file("input.txt");
+    assert_eq!(line.error(6, 1, "a digit").width, 1);
+    assert_eq!(
+      err.to_string(),
+      "expected a rotation distance\n  --> input.txt:12:6\n   |\n12 | L68,R1x\n   |      ^^"
+    );
+  }
+
+  #[test]
+  fn test_lines() {
+    let lines = lines(&b"a\n\xff\nc"[..]).collect::<Vec<_>>();
+    assert_eq!(
+      lines[0],
+      Ok(Line {
+        number: 1,
+        text:   "a".to_string(),
+      })
+    );
+    assert_eq!(lines[1].as_ref().unwrap_err().line, 2);
+    assert_eq!(lines[2].as_ref().unwrap().number, 3);
+  }
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::config::Workspace;
+use crate::{App, GenArgs, InputGenerator, Report, RunArgs};
+
+/// Object-safe view of an [`App`], so solvers of different days can be stored
+/// together.
+pub trait Solver: Sync {
+  fn day(&self) -> u8;
+  fn name(&self) -> &'static str;
+  fn run(&self, args: RunArgs) -> Result<()>;
+  /// Solves both parts against the day's configured input, or the input `set`.
+  fn solve_configured(
+    &self,
+    workspace: &Workspace,
+    set: Option<&str>,
+    timeout: Option<Duration>,
+  ) -> Result<Vec<Report>>;
+}
+
+impl<A: App> Solver for A {
+  fn day(&self) -> u8 { A::DAY }
+
+  fn name(&self) -> &'static str { A::NAME }
+
+  fn run(&self, args: RunArgs) -> Result<()> { crate::run_with(*self, args) }
+
+  fn solve_configured(
+    &self,
+    workspace: &Workspace,
+    set: Option<&str>,
+    timeout: Option<Duration>,
+  ) -> Result<Vec<Report>> {
+    crate::solve_configured(*self, workspace, set, timeout)
+  }
+}
+
+/// Object-safe view of an [`InputGenerator`].
+pub trait Generator: Solver {
+  fn generate(&self, args: GenArgs) -> Result<()>;
+}
+
+impl<A: InputGenerator> Generator for A {
+  fn generate(&self, args: GenArgs) -> Result<()> { crate::generate::run(*self, args) }
+}
+
+#[derive(Default)]
+pub struct Registry {
+  solvers:    Vec<Box<dyn Solver>>,
+  generators: Vec<Box<dyn Generator>>,
+}
+
+impl Registry {
+  pub fn new() -> Self { Self::default() }
+
+  pub fn register(mut self, app: impl App) -> Self {
+    self.solvers.push(Box::new(app));
+    self.solvers.sort_by_key(|solver| solver.day());
+    self
+  }
+
+  /// Registers `app` along with its input generator.
+  pub fn register_with_generator(mut self, app: impl InputGenerator) -> Self {
+    self.generators.push(Box::new(app));
+    self.register(app)
+  }
+
+  /// Looks a solver up either by its day number or by its name.
+  pub fn find(&self, day: &str) -> Option<&dyn Solver> {
+    self
+      .solvers
+      .iter()
+      .find(|solver| is_day(solver.as_ref(), day))
+      .map(Box::as_ref)
+  }
+
+  /// Looks an input generator up like [`Registry::find`].
+  pub fn find_generator(&self, day: &str) -> Option<&dyn Generator> {
+    self
+      .generators
+      .iter()
+      .find(|generator| is_day(generator.as_ref(), day))
+      .map(Box::as_ref)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &dyn Solver> { self.solvers.iter().map(Box::as_ref) }
+}
+
+fn is_day(solver: &(impl Solver + ?Sized), day: &str) -> bool {
+  solver.name() == day || solver.day().to_string() == day
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::test_day::Day;
+
+  #[test]
+  fn test_register_keeps_days_ordered() {
+    let registry = Registry::new().register(Day::<3>).register(Day::<1>);
+    assert_eq!(registry.iter().map(|solver| solver.day()).collect::<Vec<_>>(), vec![
+      1, 3
+    ]);
+  }
+
+  #[test]
+  fn test_find() {
+    let registry = Registry::new().register(Day::<2>);
+    assert_eq!(registry.find("2").map(|solver| solver.day()), Some(2));
+    assert_eq!(registry.find("test-day").map(|solver| solver.day()), Some(2));
+    assert!(registry.find("3").is_none());
+  }
+}
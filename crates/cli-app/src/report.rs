@@ -0,0 +1,284 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::Part;
+use crate::expect::Expected;
+use crate::memory::Allocations;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum Format {
+  #[default]
+  Text,
+  Json,
+  Csv,
+}
+
+/// Outcome of solving a single part, as printed by the structured formats.
+#[derive(Serialize, Clone, Debug)]
+pub struct Report {
+  pub day:         u8,
+  pub part:        &'static str,
+  pub variant:     &'static str,
+  pub input:       String,
+  pub answer:      Option<String>,
+  /// Known answer the solver output was checked against.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub expected:    Option<String>,
+  pub parse_ns:    Option<u64>,
+  pub solve_ns:    Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub parse_alloc: Option<Allocations>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub solve_alloc: Option<Allocations>,
+  pub error:       Option<Failure>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Failure {
+  pub kind:    FailureKind,
+  pub message: String,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureKind {
+  Parse,
+  Solve,
+  Mismatch,
+  Disagreement,
+  Timeout,
+  Regression,
+  /// The input or the ledger could not be read or written.
+  Io,
+  /// `aoc.toml` or an option such as `--param` is invalid.
+  Config,
+}
+
+impl Report {
+  pub fn new(day: u8, part: &'static str, variant: &'static str, input: &str) -> Self {
+    Report {
+      day,
+      part,
+      variant,
+      input: input.to_string(),
+      answer: None,
+      expected: None,
+      parse_ns: None,
+      solve_ns: None,
+      parse_alloc: None,
+      solve_alloc: None,
+      error: None,
+    }
+  }
+
+  pub fn parsed_in(&mut self, elapsed: Duration) { self.parse_ns = Some(elapsed.as_nanos() as u64) }
+
+  pub fn solved_in(&mut self, elapsed: Duration) { self.solve_ns = Some(elapsed.as_nanos() as u64) }
+
+  /// Records the known answer for the part and fails the report when it
+  /// differs from the solver output.
+  pub fn check(&mut self, part: Part, expected: &Expected) {
+    self.expected = expected.answer(part).map(str::to_string);
+    if let Some(mismatch) = self.answer.as_deref().and_then(|answer| expected.check(part, answer)) {
+      self.fail(FailureKind::Mismatch, mismatch);
+    }
+  }
+
+  pub fn fail(&mut self, kind: FailureKind, message: impl Display) {
+    self.error = Some(Failure {
+      kind,
+      message: message.to_string(),
+    })
+  }
+}
+
+pub struct Printer {
+  format:       Format,
+  labelled:     bool,
+  wrote_header: bool,
+}
+
+impl Printer {
+  /// `labelled` prefixes text answers with their part, for runs solving both
+  /// parts.
+  pub fn new(format: Format, labelled: bool) -> Self {
+    Printer {
+      format,
+      labelled,
+      wrote_header: false,
+    }
+  }
+
+  pub fn print(&mut self, report: &Report) -> Result<()> { self.print_change(report, None) }
+
+  /// Prints `report`, followed in text format by how its answer moved since
+  /// the `previous` one.
+  pub fn print_change(&mut self, report: &Report, previous: Option<&str>) -> Result<()> {
+    match self.format {
+      Format::Text => {
+        if let Some(answer) = &report.answer {
+          let change = previous.map(|previous| format!(" ({})", change(previous, answer)));
+          let change = change.as_deref().unwrap_or_default();
+          if self.labelled {
+            println!("Part {}: {answer}{change}", report.part);
+          } else {
+            println!("Result: {answer}{change}");
+          }
+        }
+        if let Some(allocations) = &report.parse_alloc {
+          println!("  parse: {allocations}");
+        }
+        if let Some(allocations) = &report.solve_alloc {
+          println!("  solve: {allocations}");
+        }
+      }
+      Format::Json => println!("{}", serde_json::to_string(report)?),
+      Format::Csv => {
+        if !self.wrote_header {
+          print!("day,part,variant,input,answer,parse_ns,solve_ns,error_kind,error_message");
+          if cfg!(feature = "alloc-stats") {
+            print!(",parse_allocs,parse_bytes,parse_peak,solve_allocs,solve_bytes,solve_peak");
+          }
+          println!();
+          self.wrote_header = true;
+        }
+        println!("{}", csv_row(report));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Describes an answer going from `previous` to `answer`, as a difference when
+/// both are numbers.
+fn change(previous: &str, answer: &str) -> String {
+  if previous == answer {
+    return "unchanged".to_string();
+  }
+  match (previous.parse::<i128>(), answer.parse::<i128>()) {
+    (Ok(previous), Ok(answer)) => format!("{:+} from {previous}", answer - previous),
+    _ => format!("was {previous}"),
+  }
+}
+
+fn csv_row(report: &Report) -> String {
+  let kind = report.error.as_ref().map(|error| match error.kind {
+    FailureKind::Parse => "parse",
+    FailureKind::Solve => "solve",
+    FailureKind::Mismatch => "mismatch",
+    FailureKind::Disagreement => "disagreement",
+    FailureKind::Timeout => "timeout",
+    FailureKind::Regression => "regression",
+    FailureKind::Io => "io",
+    FailureKind::Config => "config",
+  });
+  [
+    report.day.to_string(),
+    report.part.to_string(),
+    report.variant.to_string(),
+    csv_field(&report.input),
+    csv_field(report.answer.as_deref().unwrap_or_default()),
+    report.parse_ns.map(|ns| ns.to_string()).unwrap_or_default(),
+    report.solve_ns.map(|ns| ns.to_string()).unwrap_or_default(),
+    kind.unwrap_or_default().to_string(),
+    csv_field(
+      report
+        .error
+        .as_ref()
+        .map(|error| error.message.as_str())
+        .unwrap_or_default(),
+    ),
+  ]
+  .into_iter()
+  .chain(
+    [report.parse_alloc, report.solve_alloc]
+      .into_iter()
+      .filter(|_| cfg!(feature = "alloc-stats"))
+      .flat_map(|allocations| match allocations {
+        Some(a) => [a.count, a.bytes, a.peak].map(|n| n.to_string()),
+        None => Default::default(),
+      }),
+  )
+  .collect::<Vec<_>>()
+  .join(",")
+}
+
+fn csv_field(s: &str) -> String {
+  if s.contains([',', '"', '\n']) {
+    format!("\"{}\"", s.replace('"', "\"\""))
+  } else {
+    s.to_string()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  /// Appends the allocation columns written when `alloc-stats` is enabled.
+  fn unmeasured(row: &str) -> String {
+    if cfg!(feature = "alloc-stats") {
+      format!("{row},,,,,,")
+    } else {
+      row.to_string()
+    }
+  }
+
+  #[test]
+  fn test_csv_row() {
+    let mut report = Report::new(1, "one", "default", "input.txt");
+    report.answer = Some("3".to_string());
+    report.parsed_in(Duration::from_nanos(10));
+    report.solved_in(Duration::from_nanos(20));
+    assert_eq!(csv_row(&report), unmeasured("1,one,default,input.txt,3,10,20,,"));
+
+    let mut report = Report::new(2, "two", "default", "-");
+    report.fail(FailureKind::Parse, "expected `a-b`, got \"1,2\"");
+    assert_eq!(
+      csv_row(&report),
+      unmeasured("2,two,default,-,,,,parse,\"expected `a-b`, got \"\"1,2\"\"\"")
+    );
+
+    let mut report = Report::new(2, "-", "-", "missing.txt");
+    report.fail(FailureKind::Io, "cannot open file `missing.txt`.");
+    assert_eq!(
+      csv_row(&report),
+      unmeasured("2,-,-,missing.txt,,,,io,cannot open file `missing.txt`.")
+    );
+  }
+
+  #[test]
+  fn test_change() {
+    assert_eq!(change("13", "13"), "unchanged");
+    assert_eq!(change("13", "15"), "+2 from 13");
+    assert_eq!(change("43", "40"), "-3 from 43");
+    assert_eq!(change("abc", "abd"), "was abc");
+  }
+
+  #[cfg(feature = "alloc-stats")]
+  #[test]
+  fn test_csv_row_allocations() {
+    let mut report = Report::new(4, "two", "grid", "-");
+    report.answer = Some("43".to_string());
+    report.parse_alloc = Some(Allocations {
+      count: 1,
+      bytes: 2,
+      peak:  3,
+    });
+    assert_eq!(csv_row(&report), "4,two,grid,-,43,,,,,1,2,3,,,");
+  }
+
+  #[test]
+  fn test_json() {
+    let mut report = Report::new(4, "one", "grid", "-");
+    report.fail(FailureKind::Solve, "boom");
+    assert_eq!(
+      serde_json::to_string(&report).unwrap(),
+      r#"{"day":4,"part":"one","variant":"grid","input":"-","answer":null,"parse_ns":null,"solve_ns":null,"error":{"kind":"solve","message":"boom"}}"#
+    );
+  }
+}
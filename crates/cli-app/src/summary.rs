@@ -0,0 +1,168 @@
+use std::fmt::Write;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+
+use crate::config::Workspace;
+use crate::{AllArgs, Registry, Report};
+
+/// Outcome of solving every part of a single day.
+struct Row {
+  day:     u8,
+  name:    &'static str,
+  reports: Result<Vec<Report>>,
+}
+
+/// Solves every registered day in parallel against its configured input and
+/// prints a table of the answers, failing if any day failed.
+pub fn run_all(registry: &Registry, args: AllArgs) -> Result<()> {
+  let workspace = Workspace::discover()?;
+  let set = args.input.as_deref();
+  let rows = thread::scope(|scope| {
+    let workers = registry
+      .iter()
+      .map(|solver| {
+        let workspace = &workspace;
+        scope.spawn(move || Row {
+          day:     solver.day(),
+          name:    solver.name(),
+          reports: solver.solve_configured(workspace, set, args.timeout),
+        })
+      })
+      .collect::<Vec<_>>();
+    workers
+      .into_iter()
+      .map(|worker| worker.join().expect("solver thread panicked"))
+      .collect::<Vec<_>>()
+  });
+
+  print!("{}", table(&rows));
+
+  let failures = rows
+    .iter()
+    .flat_map(|row| match &row.reports {
+      Ok(reports) => reports
+        .iter()
+        .filter_map(|report| Some(format!("{}: {}", row.name, report.error.as_ref()?.message)))
+        .collect(),
+      Err(err) => vec![format!("{}: {err:#}", row.name)],
+    })
+    .collect::<Vec<_>>();
+  if !failures.is_empty() {
+    bail!("{}", failures.join("\n"));
+  }
+
+  Ok(())
+}
+
+fn table(rows: &[Row]) -> String {
+  let mut lines = vec![[
+    "Day".to_string(),
+    "Name".to_string(),
+    "Part".to_string(),
+    "Answer".to_string(),
+    "Parse".to_string(),
+    "Solve".to_string(),
+    "Status".to_string(),
+  ]];
+  for row in rows {
+    let cells = |part: &str, answer: &str, parse: String, solve: String, status: &str| {
+      [
+        row.day.to_string(),
+        row.name.to_string(),
+        part.to_string(),
+        answer.to_string(),
+        parse,
+        solve,
+        status.to_string(),
+      ]
+    };
+    match &row.reports {
+      Ok(reports) => lines.extend(reports.iter().map(|report| {
+        cells(
+          report.part,
+          report.answer.as_deref().unwrap_or("-"),
+          duration(report.parse_ns),
+          duration(report.solve_ns),
+          status(report),
+        )
+      })),
+      Err(_) => lines.push(cells("-", "-", "-".to_string(), "-".to_string(), "error")),
+    }
+  }
+
+  let widths = (0..7)
+    .map(|column| lines.iter().map(|line| line[column].chars().count()).max().unwrap_or(0))
+    .collect::<Vec<_>>();
+  let mut table = String::new();
+  for line in lines {
+    for (column, (cell, width)) in line.iter().zip(&widths).enumerate() {
+      let separator = if column == 0 { "" } else { "  " };
+      // Numbers are right-aligned, text is left-aligned.
+      match column {
+        0 | 4 | 5 => write!(table, "{separator}{cell:>width$}"),
+        _ => write!(table, "{separator}{cell:<width$}"),
+      }
+      .unwrap();
+    }
+    table.truncate(table.trim_end().len());
+    table.push('\n');
+  }
+  table
+}
+
+fn duration(ns: Option<u64>) -> String {
+  ns.map(|ns| format!("{:.2?}", Duration::from_nanos(ns)))
+    .unwrap_or_else(|| "-".to_string())
+}
+
+fn status(report: &Report) -> &'static str {
+  match (&report.error, &report.expected) {
+    (Some(_), _) => "FAIL",
+    (None, Some(_)) => "pass",
+    (None, None) => "unchecked",
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use anyhow::anyhow;
+
+  use super::*;
+  use crate::report::FailureKind;
+
+  #[test]
+  fn test_table() {
+    let mut passed = Report::new(3, "one", "default", "lobby.txt");
+    passed.answer = Some("357".to_string());
+    passed.expected = Some("357".to_string());
+    passed.parse_ns = Some(1_500);
+    passed.solve_ns = Some(2_000_000);
+    let mut failed = Report::new(3, "two", "default", "lobby.txt");
+    failed.answer = Some("12".to_string());
+    failed.fail(FailureKind::Mismatch, "answer mismatch");
+
+    let rows = [
+      Row {
+        day:     3,
+        name:    "lobby",
+        reports: Ok(vec![passed, failed]),
+      },
+      Row {
+        day:     4,
+        name:    "printing-department",
+        reports: Err(anyhow!("no input")),
+      },
+    ];
+    assert_eq!(
+      table(&rows),
+      "\
+Day  Name                 Part  Answer   Parse   Solve  Status
+  3  lobby                one   357     1.50µs  2.00ms  pass
+  3  lobby                two   12           -       -  FAIL
+  4  printing-department  -     -            -       -  error
+"
+    );
+  }
+}
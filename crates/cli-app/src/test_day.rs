@@ -0,0 +1,61 @@
+//! A dummy day shared by the tests of the crate.
+
+use std::io::BufRead;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use anyhow::Result;
+
+use crate::{App, CancellationToken, ParseError, Part, Variant};
+
+/// Set once a part one spinning on the `spin` input noticed its cancellation.
+pub static SPIN_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Day `N`, whose both parts answer `N` unless the input tells them otherwise:
+/// part one spins until it is cancelled on `spin` and panics on `panic`, and
+/// parsing panics on `unparsable`. Part one has a `fast` and a `slow` variant.
+#[derive(Clone, Copy)]
+pub struct Day<const N: u8>;
+
+impl<const N: u8> App for Day<N> {
+  type Input = String;
+  type Output = u8;
+  type Params = ();
+
+  const DAY: u8 = N;
+  const NAME: &'static str = "test-day";
+
+  fn parse_input(self, mut buf: impl BufRead) -> Result<Self::Input, ParseError> {
+    let mut text = String::new();
+    buf.read_to_string(&mut text).unwrap();
+    assert_ne!(text, "unparsable", "unexpected input");
+    Ok(text)
+  }
+
+  fn solve_part_one(self, input: Self::Input, _: &()) -> Result<Self::Output> {
+    match input.as_str() {
+      "spin" => {
+        let cancellation = CancellationToken::current();
+        while !cancellation.is_cancelled() {
+          thread::yield_now();
+        }
+        SPIN_CANCELLED.store(true, Ordering::Relaxed);
+      }
+      "panic" => panic!("overflow"),
+      _ => {}
+    }
+    Ok(N)
+  }
+
+  fn solve_part_two(self, _: Self::Input, _: &()) -> Result<Self::Output> { Ok(N) }
+
+  fn variants(part: Part) -> Vec<Variant<Self>> {
+    match part {
+      Part::First => vec![
+        Variant::new("fast", Self::solve_part_one),
+        Variant::new("slow", |_, _, _| Ok(N)),
+      ],
+      _ => vec![Variant::new("default", Self::solve_part_two)],
+    }
+  }
+}
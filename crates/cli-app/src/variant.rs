@@ -0,0 +1,96 @@
+use anyhow::{Result, bail};
+
+use crate::{App, Part};
+
+/// Solver of one part of day `A`, with the signature of
+/// [`App::solve_part_one`].
+pub type SolveFn<A> = fn(A, <A as App>::Input, &<A as App>::Params) -> Result<<A as App>::Output>;
+
+/// A named solver for one part of a day.
+pub struct Variant<A: App> {
+  pub name:  &'static str,
+  pub solve: SolveFn<A>,
+}
+
+impl<A: App> Clone for Variant<A> {
+  fn clone(&self) -> Self { *self }
+}
+
+impl<A: App> Copy for Variant<A> {}
+
+impl<A: App> Variant<A> {
+  pub fn new(name: &'static str, solve: SolveFn<A>) -> Self { Variant { name, solve } }
+
+  /// Picks the variants to run for each of `parts`: all of them when
+  /// cross-checking, otherwise the one called `name` for the parts that have
+  /// it and the default one for the others. Fails when none of the parts has a
+  /// variant called `name`.
+  pub fn select(parts: &[Part], name: Option<&str>, cross_check: bool) -> Result<Vec<(Part, Vec<Self>)>> {
+    let mut found = false;
+    let mut available = Vec::new();
+    let plan = parts
+      .iter()
+      .map(|&part| {
+        let variants = A::variants(part);
+        if cross_check {
+          return (part, variants);
+        }
+        let named = variants.iter().position(|variant| Some(variant.name) == name);
+        found |= named.is_some();
+        for variant in &variants {
+          if !available.contains(&variant.name) {
+            available.push(variant.name);
+          }
+        }
+        (part, variants.into_iter().skip(named.unwrap_or(0)).take(1).collect())
+      })
+      .collect();
+
+    match name {
+      Some(name) if !cross_check && !found => {
+        let parts = parts.iter().map(|part| part.name()).collect::<Vec<_>>();
+        bail!(
+          "no variant `{name}` for part {}, available: {}.",
+          parts.join(" or "),
+          available.join(", ")
+        )
+      }
+      _ => Ok(plan),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::test_day::Day;
+
+  fn names(plan: Vec<(Part, Vec<Variant<Day<1>>>)>) -> Vec<Vec<&'static str>> {
+    plan
+      .into_iter()
+      .map(|(_, variants)| variants.iter().map(|variant| variant.name).collect())
+      .collect()
+  }
+
+  #[test]
+  fn test_select() {
+    let both = Part::Both.parts();
+    assert_eq!(names(Variant::select(&[Part::First], None, false).unwrap()), vec![
+      vec!["fast"]
+    ]);
+    assert_eq!(names(Variant::select(both, Some("slow"), false).unwrap()), vec![
+      vec!["slow"],
+      vec!["default"]
+    ]);
+    assert_eq!(names(Variant::select(both, None, true).unwrap()), vec![
+      vec!["fast", "slow"],
+      vec!["default"]
+    ]);
+    assert!(Variant::<Day<1>>::select(&[Part::Second], Some("slow"), false).is_err());
+    let err = Variant::<Day<1>>::select(both, Some("other"), false).err().unwrap();
+    assert_eq!(
+      err.to_string(),
+      "no variant `other` for part one or two, available: fast, slow, default."
+    );
+  }
+}
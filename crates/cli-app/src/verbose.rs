@@ -0,0 +1,29 @@
+//! `-v`/`-vv` output of the `tracing` spans and events, on stderr so the
+//! answers on stdout stay clean.
+//!
+//! The runner wraps parsing and solving in `parse` and `solve` spans, and the
+//! days emit domain events inside them: `-v` shows the debug events and how
+//! long every span took, `-vv` adds the trace events of every single step.
+
+use std::io;
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Installs the stderr subscriber for `verbosity` occurrences of `-v`. Without
+/// any, no subscriber is installed and events cost next to nothing.
+pub(crate) fn init(verbosity: u8) {
+  let level = match verbosity {
+    0 => return,
+    1 => LevelFilter::DEBUG,
+    _ => LevelFilter::TRACE,
+  };
+  // Fails when a subscriber is already installed, e.g. by an earlier run in the
+  // same process, which is fine.
+  let _ = tracing_subscriber::fmt()
+    .with_writer(io::stderr)
+    .with_max_level(level)
+    .with_span_events(FmtSpan::CLOSE)
+    .with_target(false)
+    .try_init();
+}
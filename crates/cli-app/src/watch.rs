@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, thread};
+
+use anyhow::Result;
+
+use crate::report::{Printer, Report};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Solves the content of `path` with `solve` every time the file's
+/// modification time changes, until the process is interrupted. Failures are
+/// printed to stderr instead of ending the loop.
+pub fn run(path: &Path, printer: &mut Printer, mut solve: impl FnMut(&[u8]) -> Vec<Report>) -> Result<()> {
+  eprintln!("Watching `{}`, press Ctrl-C to stop.", path.display());
+  let mut seen = None;
+  let mut previous = HashMap::new();
+
+  loop {
+    let modified = fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+    if modified.is_none() || modified == seen {
+      thread::sleep(POLL_INTERVAL);
+      continue;
+    }
+    if seen.is_some() {
+      eprintln!("`{}` changed, solving again.", path.display());
+    }
+    seen = modified;
+
+    let input = match fs::read(path) {
+      Ok(input) => input,
+      Err(err) => {
+        eprintln!("Error: cannot open file `{}`: {err}.", path.display());
+        continue;
+      }
+    };
+    let mut errors = Vec::new();
+    for report in solve(&input) {
+      printer.print_change(&report, previous.get(report.part).map(String::as_str))?;
+      // A malformed input fails every part with the same parse error.
+      if let Some(error) = report.error
+        && !errors.contains(&error.message)
+      {
+        eprintln!("Error: {}", error.message);
+        errors.push(error.message);
+      }
+      if let Some(answer) = report.answer {
+        previous.insert(report.part, answer);
+      }
+    }
+  }
+}
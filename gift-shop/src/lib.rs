@@ -0,0 +1,182 @@
+//! Day 2: Gift Shop.
+//!
+//! The solver functions live in [`range`]; [`App`] wires them into `cli_app`.
+
+pub mod range;
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{Result, bail, ensure};
+use cli_app::{Line, ParseError, Rng};
+
+/// An inclusive range of product IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+  pub min_bound: u64,
+  pub max_bound: u64,
+}
+
+impl Range {
+  /// Parses a `lower-upper` token found at the byte `offset` of `line`.
+  fn parse(line: &Line, offset: usize, token: &str) -> Result<Self, ParseError> {
+    let Some((lower, upper)) = token.split_once('-') else {
+      return Err(line.error(offset, token.len(), "a range like `11-22`"));
+    };
+    let bound = |s: &str, at: usize| {
+      s.trim()
+        .parse::<u64>()
+        .map_err(|_| line.error(at, s.len(), "a product ID"))
+    };
+
+    Ok(Range {
+      min_bound: bound(lower, offset)?,
+      max_bound: bound(upper, offset + lower.len() + 1)?,
+    })
+  }
+}
+
+#[derive(Clone, Copy)]
+pub struct App {}
+
+impl cli_app::App for App {
+  type Input = Vec<Range>;
+  type Output = u64;
+  type Params = ();
+
+  const DAY: u8 = 2;
+  const NAME: &'static str = "gift-shop";
+
+  fn parse_input(self, buf: impl BufRead) -> Result<Self::Input, ParseError> {
+    let mut ranges = Vec::new();
+    for line in cli_app::lines(buf) {
+      let line = line?;
+      let mut offset = 0;
+      for token in line.text.split(',') {
+        if !token.trim().is_empty() {
+          ranges.push(Range::parse(&line, offset, token)?);
+        }
+        offset += token.len() + 1;
+      }
+    }
+    Ok(ranges)
+  }
+
+  fn solve_part_one(self, input: Self::Input, _params: &()) -> Result<Self::Output> {
+    Ok(
+      input
+        .into_iter()
+        .flat_map(|r| range::silly_patterns(r.min_bound, r.max_bound))
+        .sum(),
+    )
+  }
+
+  fn solve_part_two(self, input: Self::Input, _params: &()) -> Result<Self::Output> {
+    Ok(
+      input
+        .into_iter()
+        .map(|r| {
+          let part_sizes = range::possible_parts(r.min_bound, r.max_bound);
+
+          part_sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &part_size)| {
+              range::silly_n_pattern(r.min_bound, r.max_bound, part_size)
+                .filter(|&x| !part_sizes.iter().take(i).any(|&ps| range::is_n_silly(x, ps)))
+                .sum::<u64>()
+            })
+            .sum::<u64>()
+        })
+        .sum(),
+    )
+  }
+}
+
+/// Shape of the ranges written by `aoc gen`.
+#[derive(Clone, Debug)]
+pub struct Shape {
+  /// Most digits of the lower bound of a range.
+  pub max_digits: u32,
+  /// Most product IDs between the bounds of a range.
+  pub max_span:   u64,
+}
+
+impl Default for Shape {
+  fn default() -> Self {
+    Shape {
+      max_digits: 10,
+      max_span:   100_000,
+    }
+  }
+}
+
+impl cli_app::Params for Shape {
+  fn set(&mut self, key: &str, value: &str) -> Result<()> {
+    match key {
+      "max-digits" => self.max_digits = cli_app::params::value(key, value)?,
+      "max-span" => self.max_span = cli_app::params::value(key, value)?,
+      _ => bail!("unknown parameter `{key}`, expected `max-digits` or `max-span`."),
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<()> {
+    ensure!(
+      (1..=16).contains(&self.max_digits),
+      "`max-digits` must be between 1 and 16, got {}.",
+      self.max_digits
+    );
+    // Keeps the upper bounds within 17 digits, far from overflowing a `u64`.
+    ensure!(
+      self.max_span <= 10_u64.pow(16),
+      "`max-span` must be at most 10^16, got {}.",
+      self.max_span
+    );
+    Ok(())
+  }
+}
+
+/// Writes `size` comma-separated `a-b` ranges on a single line.
+impl cli_app::InputGenerator for App {
+  type Shape = Shape;
+
+  fn generate(self, size: usize, shape: &Shape, rng: &mut Rng, out: &mut dyn Write) -> io::Result<()> {
+    for i in 0..size {
+      let digits = rng.range(1..=shape.max_digits as u64) as u32;
+      let min_bound = rng.range(10_u64.pow(digits - 1)..=10_u64.pow(digits) - 1);
+      let max_bound = min_bound + rng.range(0..=shape.max_span);
+      let separator = if i == 0 { "" } else { "," };
+      write!(out, "{separator}{min_bound}-{max_bound}")?;
+    }
+    writeln!(out)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use cli_app::{App as _, Params as _};
+
+  use super::*;
+
+  #[test]
+  fn test_parse_error() {
+    let err = App {}.parse_input(&b"11-22,\n95-115,998+1012"[..]).unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (2, 8, 8));
+
+    let err = App {}.parse_input(&b"11-22,95-1x5"[..]).unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (1, 10, 3));
+  }
+
+  #[test]
+  fn test_readme_example() { cli_app::examples::check_readme(App {}, include_str!("../README.md")).unwrap() }
+
+  #[test]
+  fn test_generated_input() { cli_app::generate::check(App {}, 200, 1).unwrap() }
+
+  #[test]
+  fn test_shape() {
+    let mut shape = Shape::default();
+    shape.set("max-span", &u64::MAX.to_string()).unwrap();
+    assert!(shape.validate().is_err());
+  }
+}
@@ -1,5 +1,8 @@
 use std::iter;
 
+use cli_app::CancellationToken;
+use tracing::debug;
+
 fn digits(n: u64) -> u32 { if n == 0 { 1 } else { n.ilog10() + 1 } }
 
 fn lower_bound_half(n: u64) -> u64 {
@@ -15,9 +18,15 @@ fn lower_bound_half(n: u64) -> u64 {
 
 fn silly_number(half: u64) -> u64 { half * 10_u64.pow(digits(half)) + half }
 
+/// Invalid IDs made of a sequence repeated twice, in order. Stops early when
+/// the run is cancelled.
 pub fn silly_patterns(lower_bound: u64, upper_bound: u64) -> impl Iterator<Item = u64> {
+  let cancellation = CancellationToken::current();
   let mut current_half = lower_bound_half(lower_bound);
   iter::from_fn(move || {
+    if cancellation.is_cancelled() {
+      return None;
+    }
     let mut current = silly_number(current_half);
     while current < lower_bound {
       current_half += 1;
@@ -28,6 +37,7 @@ pub fn silly_patterns(lower_bound: u64, upper_bound: u64) -> impl Iterator<Item
       None
     } else {
       current_half += 1;
+      debug!(id = current, lower_bound, upper_bound, "invalid ID");
       Some(current)
     }
   })
@@ -39,7 +49,7 @@ fn lower_n_part(num: u64, parts: u32) -> u64 {
   let d = digits(num);
   let part_size = d / parts;
 
-  if d % parts == 0 {
+  if d.is_multiple_of(parts) {
     num / 10_u64.pow(part_size * (parts - 1))
   } else {
     10_u64.pow(part_size)
@@ -54,9 +64,15 @@ fn silly_n_number(part: u64, n: u32) -> u64 {
   })
 }
 
+/// Invalid IDs made of a sequence repeated `n` times, in order. Stops early
+/// when the run is cancelled.
 pub fn silly_n_pattern(lower_bound: u64, upper_bound: u64, n: u32) -> impl Iterator<Item = u64> {
+  let cancellation = CancellationToken::current();
   let mut current_part = lower_n_part(lower_bound, n);
   iter::from_fn(move || {
+    if cancellation.is_cancelled() {
+      return None;
+    }
     let mut current = silly_n_number(current_part, n);
     while current < lower_bound {
       current_part += 1;
@@ -67,6 +83,7 @@ pub fn silly_n_pattern(lower_bound: u64, upper_bound: u64, n: u32) -> impl Itera
       None
     } else {
       current_part += 1;
+      debug!(id = current, repeated = n, lower_bound, upper_bound, "invalid ID");
       Some(current)
     }
   })
@@ -125,7 +142,10 @@ mod test {
       1188511885
     ]);
     assert_eq!(silly_patterns(222220, 222224).collect::<Vec<u64>>(), vec![222222]);
-    assert_eq!(silly_patterns(1698522, 1698528).collect::<Vec<u64>>(), vec![]);
+    assert_eq!(
+      silly_patterns(1698522, 1698528).collect::<Vec<u64>>(),
+      Vec::<u64>::new()
+    );
   }
 
   // --- Part Two ---
@@ -192,23 +212,32 @@ mod test {
       1188511885
     ]);
     assert_eq!(silly_n_pattern(222220, 222224, 2).collect::<Vec<u64>>(), vec![222222]);
-    assert_eq!(silly_n_pattern(1698522, 1698528, 2).collect::<Vec<u64>>(), vec![]);
+    assert_eq!(
+      silly_n_pattern(1698522, 1698528, 2).collect::<Vec<u64>>(),
+      Vec::<u64>::new()
+    );
     // part_size = 3
-    assert_eq!(silly_n_pattern(11, 22, 3).collect::<Vec<u64>>(), vec![]);
+    assert_eq!(silly_n_pattern(11, 22, 3).collect::<Vec<u64>>(), Vec::<u64>::new());
     assert_eq!(silly_n_pattern(95, 115, 3).collect::<Vec<u64>>(), vec![111]);
     assert_eq!(silly_n_pattern(998, 1012, 3).collect::<Vec<u64>>(), vec![999]);
-    assert_eq!(silly_n_pattern(1188511880, 1188511890, 3).collect::<Vec<u64>>(), vec![]);
+    assert_eq!(
+      silly_n_pattern(1188511880, 1188511890, 3).collect::<Vec<u64>>(),
+      Vec::<u64>::new()
+    );
     assert_eq!(silly_n_pattern(222220, 222224, 3).collect::<Vec<u64>>(), vec![222222]);
     assert_eq!(silly_n_pattern(565653, 565659, 3).collect::<Vec<u64>>(), vec![565656]);
-    assert_eq!(silly_n_pattern(1698522, 1698528, 3).collect::<Vec<u64>>(), vec![]);
+    assert_eq!(
+      silly_n_pattern(1698522, 1698528, 3).collect::<Vec<u64>>(),
+      Vec::<u64>::new()
+    );
     // part_size = 5
-    assert_eq!(silly_n_pattern(95, 115, 5).collect::<Vec<u64>>(), vec![]);
+    assert_eq!(silly_n_pattern(95, 115, 5).collect::<Vec<u64>>(), Vec::<u64>::new());
     assert_eq!(silly_n_pattern(998, 12012, 5).collect::<Vec<u64>>(), vec![11111]);
     assert_eq!(silly_n_pattern(1200000000, 1288511890, 5).collect::<Vec<u64>>(), vec![
       1212121212
     ]);
     // part_size = 7
-    assert_eq!(silly_n_pattern(95, 115, 7).collect::<Vec<u64>>(), vec![]);
+    assert_eq!(silly_n_pattern(95, 115, 7).collect::<Vec<u64>>(), Vec::<u64>::new());
     assert_eq!(silly_n_pattern(998, 2301200, 7).collect::<Vec<u64>>(), vec![
       1111111, 2222222
     ]);
@@ -5,6 +5,8 @@
 //! According to the task definition, the solution will be a variation of the previous one, with a slight twist: we need 12 pointers.
 //! Instead of writing an "exact 12 pointers" solution, we can abstract to an N-pointers solution, where N is a positive number.
 
+use tracing::debug;
+
 static ZERO: u8 = b'0';
 
 pub fn banks_max(bank: &str) -> u64 {
@@ -12,9 +14,14 @@ pub fn banks_max(bank: &str) -> u64 {
   let mut i = 0;
   let mut j = 1;
   let mut joltage = 0;
+  let mut chosen = (0, 1);
 
   while j < bank_bytes.len() {
-    joltage = joltage.max(calc_joltage_for(bank_bytes, i, j));
+    let candidate = calc_joltage_for(bank_bytes, i, j);
+    if candidate > joltage {
+      joltage = candidate;
+      chosen = (i, j);
+    }
 
     if bank_bytes[i] < bank_bytes[j] {
       i = j;
@@ -22,6 +29,7 @@ pub fn banks_max(bank: &str) -> u64 {
     j += 1;
   }
 
+  debug!(bank, batteries = ?chosen, joltage, "chosen batteries");
   joltage
 }
 
@@ -39,9 +47,15 @@ pub fn banks_n_max(bank: &str, n: usize) -> u64 {
   let mut batteries_vec: Vec<usize> = (0..n).collect();
   let batteries: &mut [usize] = &mut batteries_vec;
   let mut joltage = 0;
+  let mut chosen = Vec::with_capacity(n);
 
   while batteries[n - 1] < bank_bytes.len() {
-    joltage = joltage.max(calc_joltage_n_for(bank_bytes, batteries));
+    let candidate = calc_joltage_n_for(bank_bytes, batteries);
+    if candidate > joltage {
+      joltage = candidate;
+      chosen.clear();
+      chosen.extend_from_slice(batteries);
+    }
 
     if let Some(mut i) = (0..n - 1).find(|&i| bank_bytes[batteries[i + 1]] > bank_bytes[batteries[i]]) {
       while i < n - 1 {
@@ -52,6 +66,7 @@ pub fn banks_n_max(bank: &str, n: usize) -> u64 {
     batteries[n - 1] += 1;
   }
 
+  debug!(bank, batteries = ?chosen, joltage, "chosen batteries");
   joltage
 }
 
@@ -0,0 +1,156 @@
+//! Day 3: Lobby.
+//!
+//! The solver functions live in [`joltage`]; [`App`] wires them into `cli_app`.
+
+pub mod joltage;
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{Result, bail, ensure};
+use cli_app::{ParseError, Part, Rng, Variant};
+
+#[derive(Clone, Copy)]
+pub struct App {}
+
+#[derive(Clone, Debug)]
+pub struct Params {
+  /// Batteries turned on in each bank in part two.
+  pub batteries: usize,
+}
+
+impl Default for Params {
+  fn default() -> Self { Params { batteries: 12 } }
+}
+
+impl cli_app::Params for Params {
+  fn set(&mut self, key: &str, value: &str) -> Result<()> {
+    match key {
+      "batteries" => self.batteries = cli_app::params::value(key, value)?,
+      _ => bail!("unknown parameter `{key}`, expected `batteries`."),
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<()> {
+    // The joltage of more than 19 batteries does not fit in a `u64`.
+    ensure!(
+      (1..=19).contains(&self.batteries),
+      "`batteries` must be between 1 and 19, got {}.",
+      self.batteries
+    );
+    Ok(())
+  }
+}
+
+impl cli_app::App for App {
+  type Input = Vec<String>;
+  type Output = u64;
+  type Params = Params;
+
+  const DAY: u8 = 3;
+  const NAME: &'static str = "lobby";
+
+  fn parse_input(self, buf: impl BufRead) -> Result<Self::Input, ParseError> {
+    cli_app::lines(buf)
+      .map(|line| {
+        let line = line?;
+        match line.text.find(|c: char| !c.is_ascii_digit()) {
+          Some(at) => Err(line.error(at, 1, "a battery joltage digit")),
+          None => Ok(line.text),
+        }
+      })
+      .collect()
+  }
+
+  fn solve_part_one(self, input: Self::Input, _params: &Params) -> anyhow::Result<Self::Output> {
+    Ok(input.iter().map(|bank| joltage::banks_max(bank)).sum::<u64>())
+  }
+
+  fn solve_part_two(self, input: Self::Input, params: &Params) -> anyhow::Result<Self::Output> {
+    Ok(
+      input
+        .iter()
+        .map(|bank| joltage::banks_n_max(bank, params.batteries))
+        .sum::<u64>(),
+    )
+  }
+
+  fn variants(part: Part) -> Vec<Variant<Self>> {
+    match part {
+      Part::First => vec![
+        Variant::new("two-pointers", Self::solve_part_one),
+        Variant::new("n-pointers", App::banks_max_with_n_pointers),
+      ],
+      Part::Second => vec![Variant::new("n-pointers", Self::solve_part_two)],
+      Part::Both => Vec::new(),
+    }
+  }
+}
+
+impl App {
+  fn banks_max_with_n_pointers(self, input: Vec<String>, _params: &Params) -> Result<u64> {
+    Ok(input.iter().map(|bank| joltage::banks_n_max(bank, 2)).sum::<u64>())
+  }
+}
+
+/// Shape of the banks written by `aoc gen`.
+#[derive(Clone, Debug)]
+pub struct Shape {
+  /// Batteries in every bank.
+  pub length: usize,
+}
+
+impl Default for Shape {
+  fn default() -> Self { Shape { length: 100 } }
+}
+
+impl cli_app::Params for Shape {
+  fn set(&mut self, key: &str, value: &str) -> Result<()> {
+    match key {
+      "length" => self.length = cli_app::params::value(key, value)?,
+      _ => bail!("unknown parameter `{key}`, expected `length`."),
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<()> {
+    ensure!(self.length > 0, "`length` must be positive.");
+    Ok(())
+  }
+}
+
+/// Writes `size` banks of random joltage digits from 1 to 9.
+impl cli_app::InputGenerator for App {
+  type Shape = Shape;
+
+  fn generate(self, size: usize, shape: &Shape, rng: &mut Rng, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+      let bank = (0..shape.length)
+        .map(|_| char::from(b'0' + rng.range(1..=9) as u8))
+        .collect::<String>();
+      writeln!(out, "{bank}")?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use cli_app::App as _;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_error() {
+    let err = App {}
+      .parse_input("987654321111111\n81111ä111111119".as_bytes())
+      .unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (2, 6, 1));
+  }
+
+  #[test]
+  fn test_readme_example() { cli_app::examples::check_readme(App {}, include_str!("../README.md")).unwrap() }
+
+  #[test]
+  fn test_generated_input() { cli_app::generate::check(App {}, 200, 1).unwrap() }
+}
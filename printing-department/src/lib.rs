@@ -0,0 +1,207 @@
+//! Day 4: Printing Department.
+//!
+//! The solver functions live in [`warehouse`]; [`App`] wires them into
+//! `cli_app`.
+
+pub mod warehouse;
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{Result, bail, ensure};
+use cli_app::{Line, ParseError, Part, Rng, Variant};
+
+use crate::warehouse::cell::{ACCESSIBLE_EMPTY_NEIGHBORS, Cell};
+use crate::warehouse::{accessible_rolls, removable_rolls};
+
+#[derive(Clone, Copy)]
+pub struct App;
+
+#[derive(Clone, Debug)]
+pub struct Params {
+  /// Empty cells, out of the eight around it, that make a roll accessible.
+  pub empty_neighbors: usize,
+}
+
+impl Default for Params {
+  fn default() -> Self {
+    Params {
+      empty_neighbors: ACCESSIBLE_EMPTY_NEIGHBORS,
+    }
+  }
+}
+
+impl cli_app::Params for Params {
+  fn set(&mut self, key: &str, value: &str) -> Result<()> {
+    match key {
+      "empty-neighbors" => self.empty_neighbors = cli_app::params::value(key, value)?,
+      _ => bail!("unknown parameter `{key}`, expected `empty-neighbors`."),
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<()> {
+    ensure!(
+      (1..=8).contains(&self.empty_neighbors),
+      "`empty-neighbors` must be between 1 and 8, got {}.",
+      self.empty_neighbors
+    );
+    Ok(())
+  }
+}
+
+impl cli_app::App for App {
+  type Input = Vec<Vec<Cell>>;
+  type Output = u32;
+  type Params = Params;
+
+  const DAY: u8 = 4;
+  const NAME: &'static str = "printing-department";
+
+  /// Parses the diagram into rows of cells, which both warehouse engines start
+  /// from.
+  fn parse_input(self, buf: impl BufRead) -> Result<Self::Input, ParseError> {
+    let mut rows = Vec::new();
+    let mut width = None;
+
+    for line in cli_app::lines(buf) {
+      let line = line?;
+      let row = line
+        .text
+        .char_indices()
+        .map(|(at, ch)| Cell::try_from(ch).map_err(|_| line.error(at, 1, "an empty cell `.` or a roll of paper `@`")))
+        .collect::<Result<Vec<_>, _>>()?;
+      let width = *width.get_or_insert(row.len());
+      if row.len() != width {
+        let at = width.min(row.len());
+        return Err(line.error(at, row.len() - at, format!("a row of {width} cells")));
+      }
+      rows.push(row);
+    }
+
+    if width.is_none() {
+      let line = Line {
+        number: 1,
+        text:   String::new(),
+      };
+      return Err(line.error(0, 0, "a diagram of at least one row"));
+    }
+    Ok(rows)
+  }
+
+  fn solve_part_one(self, input: Self::Input, params: &Params) -> Result<Self::Output> {
+    Ok(
+      accessible_rolls::Warehouse::from_rows(input)
+        .with_threshold(params.empty_neighbors)
+        .count_accessible_rolls(),
+    )
+  }
+
+  fn solve_part_two(self, input: Self::Input, params: &Params) -> Result<Self::Output> {
+    Ok(
+      removable_rolls::Warehouse::from_rows(input)
+        .with_threshold(params.empty_neighbors)
+        .count_removable_rolls(),
+    )
+  }
+
+  /// Part one can be answered by either grid engine: the streaming one looks
+  /// at two rows at a time, the whole-grid one reports its first removal wave.
+  fn variants(part: Part) -> Vec<Variant<Self>> {
+    match part {
+      Part::First => vec![
+        Variant::new("streaming", Self::solve_part_one),
+        Variant::new("grid", App::count_accessible_rolls_on_grid),
+      ],
+      Part::Second => vec![Variant::new("grid", Self::solve_part_two)],
+      Part::Both => Vec::new(),
+    }
+  }
+}
+
+impl App {
+  fn count_accessible_rolls_on_grid(self, input: Vec<Vec<Cell>>, params: &Params) -> Result<u32> {
+    Ok(
+      removable_rolls::Warehouse::from_rows(input)
+        .with_threshold(params.empty_neighbors)
+        .count_accessible_rolls(),
+    )
+  }
+}
+
+/// Shape of the diagram written by `aoc gen`.
+#[derive(Clone, Debug)]
+pub struct Shape {
+  /// Chance of every cell to hold a roll of paper.
+  pub density: f64,
+}
+
+impl Default for Shape {
+  fn default() -> Self { Shape { density: 0.6 } }
+}
+
+impl cli_app::Params for Shape {
+  fn set(&mut self, key: &str, value: &str) -> Result<()> {
+    match key {
+      "density" => self.density = cli_app::params::value(key, value)?,
+      _ => bail!("unknown parameter `{key}`, expected `density`."),
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<()> {
+    ensure!(
+      (0. ..=1.).contains(&self.density),
+      "`density` must be between 0 and 1, got {}.",
+      self.density
+    );
+    Ok(())
+  }
+}
+
+/// Writes a square diagram of `size` rows of `size` cells, at least one, since
+/// an empty diagram does not parse.
+impl cli_app::InputGenerator for App {
+  type Shape = Shape;
+
+  fn generate(self, size: usize, shape: &Shape, rng: &mut Rng, out: &mut dyn Write) -> io::Result<()> {
+    let size = size.max(1);
+    for _ in 0..size {
+      let row = (0..size)
+        .map(|_| if rng.chance(shape.density) { '@' } else { '.' })
+        .collect::<String>();
+      writeln!(out, "{row}")?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use cli_app::App as _;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_error() {
+    let err = App.parse_input(&b"..@\n.x.\n"[..]).unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (2, 2, 1));
+
+    let err = App.parse_input(&b"..@\n.@.@\n"[..]).unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (2, 4, 1));
+
+    let err = App.parse_input(&b""[..]).unwrap_err();
+    assert_eq!(
+      (err.line, err.column, err.expected.as_str()),
+      (1, 1, "a diagram of at least one row")
+    );
+  }
+
+  #[test]
+  fn test_readme_example() { cli_app::examples::check_readme(App, include_str!("../README.md")).unwrap() }
+
+  #[test]
+  fn test_generated_input() {
+    cli_app::generate::check(App, 100, 1).unwrap();
+    cli_app::generate::check(App, 0, 1).unwrap();
+  }
+}
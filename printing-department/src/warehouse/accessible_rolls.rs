@@ -1,60 +1,70 @@
-use std::io::{BufRead, Lines};
-use std::iter::Peekable;
+use std::io::{self, BufRead, Lines};
+use std::iter::{Map, MapWhile, Peekable};
+use std::vec;
 
 use anyhow::Result;
 
-use super::cell::Cell;
+use super::cell::{ACCESSIBLE_EMPTY_NEIGHBORS, Cell};
 
 pub type WarehouseRow = Vec<Cell>;
 
-pub struct Warehouse<B: BufRead> {
+/// Rows read from a buffer, up to the first unreadable or malformed line.
+pub type ReadRows<B> = MapWhile<Lines<B>, fn(io::Result<String>) -> Option<WarehouseRow>>;
+
+/// Rows of an already parsed diagram.
+pub type ParsedRows = Map<vec::IntoIter<Vec<Cell>>, fn(Vec<Cell>) -> WarehouseRow>;
+
+pub struct Warehouse<R: Iterator<Item = WarehouseRow>> {
   stop:      bool,
   head_row:  Option<WarehouseRow>,
-  next_rows: Peekable<Lines<B>>,
+  next_rows: Peekable<R>,
+  threshold: usize,
 }
 
-impl<B: BufRead> Warehouse<B> {
+impl<B: BufRead> Warehouse<ReadRows<B>> {
+  /// Parses the rows while it walks through them.
   pub fn new(buf: B) -> Self {
+    let parse: fn(io::Result<String>) -> Option<WarehouseRow> = |line| parse_row(&line.ok()?).ok();
+    Self::from_iter(buf.lines().map_while(parse))
+  }
+}
+
+impl Warehouse<ParsedRows> {
+  /// Walks through the rows of a diagram parsed beforehand.
+  pub fn from_rows(rows: Vec<Vec<Cell>>) -> Self {
+    let pad: fn(Vec<Cell>) -> WarehouseRow = pad_row;
+    Self::from_iter(rows.into_iter().map(pad))
+  }
+}
+
+impl<R: Iterator<Item = WarehouseRow>> Warehouse<R> {
+  fn from_iter(rows: R) -> Self {
     Self {
       head_row:  None,
       stop:      false,
-      next_rows: buf.lines().peekable(),
+      next_rows: rows.peekable(),
+      threshold: ACCESSIBLE_EMPTY_NEIGHBORS,
     }
   }
 
-  fn parse_row(&mut self, s: &str) -> Result<WarehouseRow> {
-    let mut row = self.empty_row();
-
-    let mut row_iter = row.iter_mut();
-    row_iter.next();
-
-    for (cell, ch) in row_iter.zip(s.chars()) {
-      *cell = Cell::try_from(ch)?;
-    }
-
-    Ok(row)
-  }
+  /// Makes rolls accessible once they have `threshold` empty neighbors.
+  pub fn with_threshold(self, threshold: usize) -> Self { Self { threshold, ..self } }
 
   fn empty_row(&mut self) -> WarehouseRow { vec![Cell::Empty; self.line_size()] }
 
   fn line_size(&mut self) -> usize {
     match (&self.head_row, self.next_rows.peek()) {
       (None, None) => 0,
-      (None, Some(row)) => {
-        let Ok(s) = row else {
-          return 0;
-        };
-        s.chars().count() + 2
-      }
-      (Some(row), _) => row.len(),
+      (None, Some(row)) | (Some(row), _) => row.len(),
     }
   }
 
   pub fn count_accessible_rolls(&mut self) -> u32 {
     let mut accessibl_rolls = 0;
+    let threshold = self.threshold;
     self.reduce(|mut prev, mut curr| {
       for i in 0..curr.len() {
-        accessibl_rolls += adjust_cell(i, &mut prev, &mut curr);
+        accessibl_rolls += adjust_cell(i, &mut prev, &mut curr, threshold);
       }
       curr
     });
@@ -63,14 +73,14 @@ impl<B: BufRead> Warehouse<B> {
   }
 }
 
-impl<B: BufRead> Iterator for Warehouse<B> {
+impl<R: Iterator<Item = WarehouseRow>> Iterator for Warehouse<R> {
   type Item = WarehouseRow;
 
   fn next(&mut self) -> Option<Self::Item> {
     match (self.head_row.clone(), self.next_rows.next()) {
       (None, None) => None,
-      (None, Some(row_line)) => {
-        self.head_row = Some(self.parse_row(&row_line.ok()?).ok()?);
+      (None, Some(row)) => {
+        self.head_row = Some(row);
         Some(self.empty_row())
       }
       (Some(head_row), None) => {
@@ -82,30 +92,42 @@ impl<B: BufRead> Iterator for Warehouse<B> {
         };
         Some(head_row)
       }
-      (Some(head_row), Some(row_line)) => {
-        self.head_row = self.parse_row(&row_line.ok()?).ok();
+      (Some(head_row), Some(row)) => {
+        self.head_row = Some(row);
         Some(head_row)
       }
     }
   }
 }
 
-fn adjust_cell(pos: usize, prev_row: &mut [Cell], curr_row: &mut [Cell]) -> u32 {
+fn parse_row(s: &str) -> Result<WarehouseRow> { Ok(pad_row(s.chars().map(Cell::try_from).collect::<Result<_>>()?)) }
+
+/// Surrounds a row with empty cells, so every cell of the diagram has eight
+/// neighbors.
+fn pad_row(cells: Vec<Cell>) -> WarehouseRow {
+  let mut row = Vec::with_capacity(cells.len() + 2);
+  row.push(Cell::Empty);
+  row.extend(cells);
+  row.push(Cell::Empty);
+  row
+}
+
+fn adjust_cell(pos: usize, prev_row: &mut [Cell], curr_row: &mut [Cell], threshold: usize) -> u32 {
   let mut newly_accessible = 0;
 
   let (left, current_and_right) = curr_row.split_at_mut(pos);
   let current_cell = &mut current_and_right[0];
 
   if let Some(prev_cell) = left.last_mut() {
-    newly_accessible += current_cell.adjust_neighbor(prev_cell);
+    newly_accessible += current_cell.adjust_neighbor(prev_cell, threshold);
   }
 
   if let Some(prev_pos) = pos.checked_sub(1) {
-    newly_accessible += current_cell.adjust_neighbor(&mut prev_row[prev_pos]);
+    newly_accessible += current_cell.adjust_neighbor(&mut prev_row[prev_pos], threshold);
   }
-  newly_accessible += current_cell.adjust_neighbor(&mut prev_row[pos]);
+  newly_accessible += current_cell.adjust_neighbor(&mut prev_row[pos], threshold);
   if pos + 1 < prev_row.len() {
-    newly_accessible += current_cell.adjust_neighbor(&mut prev_row[pos + 1]);
+    newly_accessible += current_cell.adjust_neighbor(&mut prev_row[pos + 1], threshold);
   }
 
   newly_accessible
@@ -121,32 +143,32 @@ mod test {
   fn test_add_empty_neighbor() {
     let mut empty_cell = Cell::Empty;
 
-    assert!(!empty_cell.add_empty_neighbor());
+    assert!(!empty_cell.add_empty_neighbor(ACCESSIBLE_EMPTY_NEIGHBORS));
 
     let mut roll_cell = Cell::Roll(0);
 
-    assert!(!roll_cell.add_empty_neighbor());
+    assert!(!roll_cell.add_empty_neighbor(ACCESSIBLE_EMPTY_NEIGHBORS));
     assert_eq!(roll_cell, Cell::Roll(1));
-    assert!(!roll_cell.add_empty_neighbor());
+    assert!(!roll_cell.add_empty_neighbor(ACCESSIBLE_EMPTY_NEIGHBORS));
     assert_eq!(roll_cell, Cell::Roll(2));
-    assert!(!roll_cell.add_empty_neighbor());
+    assert!(!roll_cell.add_empty_neighbor(ACCESSIBLE_EMPTY_NEIGHBORS));
     assert_eq!(roll_cell, Cell::Roll(3));
-    assert!(!roll_cell.add_empty_neighbor());
+    assert!(!roll_cell.add_empty_neighbor(ACCESSIBLE_EMPTY_NEIGHBORS));
     assert_eq!(roll_cell, Cell::Roll(4));
-    assert!(roll_cell.add_empty_neighbor());
+    assert!(roll_cell.add_empty_neighbor(ACCESSIBLE_EMPTY_NEIGHBORS));
     assert_eq!(roll_cell, Cell::Roll(5));
-    assert!(!roll_cell.add_empty_neighbor());
+    assert!(!roll_cell.add_empty_neighbor(ACCESSIBLE_EMPTY_NEIGHBORS));
     assert_eq!(roll_cell, Cell::Roll(6));
-    assert!(!roll_cell.add_empty_neighbor());
+    assert!(!roll_cell.add_empty_neighbor(ACCESSIBLE_EMPTY_NEIGHBORS));
     assert_eq!(roll_cell, Cell::Roll(7));
-    assert!(!roll_cell.add_empty_neighbor());
+    assert!(!roll_cell.add_empty_neighbor(ACCESSIBLE_EMPTY_NEIGHBORS));
     assert_eq!(roll_cell, Cell::Roll(8));
   }
 
   #[test]
   fn test_roll_cell_iter_from_str() {
     let s = String::from(".@a");
-    let mut iter = s.chars().map(|c| Cell::try_from(c));
+    let mut iter = s.chars().map(Cell::try_from);
 
     let first = iter.next().unwrap();
     assert!(first.is_ok());
@@ -164,47 +186,44 @@ mod test {
   fn test_adjust_neighbor() {
     let mut left = Cell::Empty;
     let mut right = Cell::Empty;
-    assert_eq!(left.adjust_neighbor(&mut right), 0);
+    assert_eq!(left.adjust_neighbor(&mut right, ACCESSIBLE_EMPTY_NEIGHBORS), 0);
 
     let mut left = Cell::Empty;
     let mut right = Cell::Roll(0);
-    assert_eq!(left.adjust_neighbor(&mut right), 0);
+    assert_eq!(left.adjust_neighbor(&mut right, ACCESSIBLE_EMPTY_NEIGHBORS), 0);
     assert_eq!(right, Cell::Roll(1));
 
     let mut left = Cell::Roll(0);
     let mut right = Cell::Empty;
-    assert_eq!(left.adjust_neighbor(&mut right), 0);
+    assert_eq!(left.adjust_neighbor(&mut right, ACCESSIBLE_EMPTY_NEIGHBORS), 0);
     assert_eq!(left, Cell::Roll(1));
 
     let mut left = Cell::Roll(0);
     let mut right = Cell::Roll(0);
-    assert_eq!(left.adjust_neighbor(&mut right), 0);
+    assert_eq!(left.adjust_neighbor(&mut right, ACCESSIBLE_EMPTY_NEIGHBORS), 0);
     assert_eq!(left, Cell::Roll(0));
     assert_eq!(right, Cell::Roll(0));
     // --------------
     let mut left = Cell::Empty;
     let mut right = Cell::Roll(4);
-    assert_eq!(left.adjust_neighbor(&mut right), 1);
+    assert_eq!(left.adjust_neighbor(&mut right, ACCESSIBLE_EMPTY_NEIGHBORS), 1);
     assert_eq!(right, Cell::Roll(5));
 
     let mut left = Cell::Roll(4);
     let mut right = Cell::Empty;
-    assert_eq!(left.adjust_neighbor(&mut right), 1);
+    assert_eq!(left.adjust_neighbor(&mut right, ACCESSIBLE_EMPTY_NEIGHBORS), 1);
     assert_eq!(left, Cell::Roll(5));
 
     let mut left = Cell::Roll(4);
     let mut right = Cell::Roll(4);
-    assert_eq!(left.adjust_neighbor(&mut right), 0);
+    assert_eq!(left.adjust_neighbor(&mut right, ACCESSIBLE_EMPTY_NEIGHBORS), 0);
     assert_eq!(left, Cell::Roll(4));
     assert_eq!(right, Cell::Roll(4));
   }
 
   #[test]
   fn test_parse_row() {
-    let buf = Cursor::new("...\n.@.");
-    let mut wh = Warehouse::new(buf);
-
-    let row = wh.parse_row("@.@");
+    let row = parse_row("@.@");
     assert!(row.is_ok());
     assert_eq!(row.unwrap(), vec![
       Cell::Empty,
@@ -214,7 +233,7 @@ mod test {
       Cell::Empty,
     ]);
 
-    let row = wh.parse_row("@..");
+    let row = parse_row("@..");
     assert!(row.is_ok());
     assert_eq!(row.unwrap(), vec![
       Cell::Empty,
@@ -224,10 +243,10 @@ mod test {
       Cell::Empty,
     ]);
 
-    let row = wh.parse_row("@.a");
+    let row = parse_row("@.a");
     assert!(row.is_err());
 
-    let row = wh.parse_row("..@");
+    let row = parse_row("..@");
     assert!(row.is_ok());
     assert_eq!(row.unwrap(), vec![
       Cell::Empty,
@@ -237,7 +256,7 @@ mod test {
       Cell::Empty,
     ]);
 
-    let row = wh.parse_row(".@.");
+    let row = parse_row(".@.");
     assert!(row.is_ok());
     assert_eq!(row.unwrap(), vec![
       Cell::Empty,
@@ -247,7 +266,7 @@ mod test {
       Cell::Empty,
     ]);
 
-    let row = wh.parse_row("...");
+    let row = parse_row("...");
     assert!(row.is_ok());
     assert_eq!(row.unwrap(), vec![
       Cell::Empty,
@@ -276,4 +295,15 @@ mod test {
 
     assert_eq!(wh.count_accessible_rolls(), 13);
   }
+
+  #[test]
+  fn test_from_rows() {
+    let rows = "...\n.@."
+      .lines()
+      .map(|row| row.chars().map(|ch| Cell::try_from(ch).unwrap()).collect())
+      .collect();
+    let mut wh = Warehouse::from_rows(rows);
+
+    assert_eq!(wh.count_accessible_rolls(), 1);
+  }
 }
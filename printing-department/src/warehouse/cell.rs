@@ -1,5 +1,10 @@
 use anyhow::{Result, anyhow};
 
+/// Empty cells, out of the eight around it, that make a roll of paper
+/// accessible: a forklift can only reach rolls with fewer than four rolls
+/// around them.
+pub const ACCESSIBLE_EMPTY_NEIGHBORS: usize = 5;
+
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Cell {
   Empty,
@@ -9,7 +14,7 @@ pub enum Cell {
 impl Cell {
   pub fn is_empty(&self) -> bool { Cell::Empty == *self }
 
-  pub fn add_empty_neighbor(&mut self) -> bool {
+  pub fn add_empty_neighbor(&mut self, threshold: usize) -> bool {
     let Cell::Roll(empty_neighbor_count) = self else {
       // An empty cell cannot became accessible
       return false
@@ -17,14 +22,14 @@ impl Cell {
     *empty_neighbor_count += 1;
 
     // Does cell became accessible
-    5 == *empty_neighbor_count
+    threshold == *empty_neighbor_count
   }
 
-  pub fn adjust_neighbor(&mut self, neighbor: &mut Cell) -> u32 {
-    if self.is_empty() && neighbor.add_empty_neighbor() {
+  pub fn adjust_neighbor(&mut self, neighbor: &mut Cell, threshold: usize) -> u32 {
+    if self.is_empty() && neighbor.add_empty_neighbor(threshold) {
       return 1;
     }
-    if neighbor.is_empty() && self.add_empty_neighbor() {
+    if neighbor.is_empty() && self.add_empty_neighbor(threshold) {
       return 1;
     }
 
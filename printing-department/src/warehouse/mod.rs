@@ -1,3 +1,3 @@
 pub mod accessible_rolls;
-mod cell;
+pub mod cell;
 pub mod removable_rolls;
@@ -1,8 +1,10 @@
 use std::io::BufRead;
 
 use anyhow::Result;
+use cli_app::CancellationToken;
+use tracing::debug;
 
-use super::cell::Cell;
+use super::cell::{ACCESSIBLE_EMPTY_NEIGHBORS, Cell};
 
 enum Arith {
   Add(usize),
@@ -37,7 +39,8 @@ static NEIGHBORS: [(Arith, Arith); 8] = [
 
 #[derive(PartialEq, Eq, Debug)]
 pub struct Warehouse {
-  plan: Vec<Vec<Cell>>,
+  plan:      Vec<Vec<Cell>>,
+  threshold: usize,
 }
 
 impl Warehouse {
@@ -45,35 +48,59 @@ impl Warehouse {
   where
     B: BufRead,
   {
-    let mut plan = Vec::new();
-    for line in buf.lines() {
-      let mut row = Warehouse::parse_row(&line?)?;
+    let rows = buf
+      .lines()
+      .map(|line| Warehouse::parse_row(&line?))
+      .collect::<Result<_>>()?;
+    Ok(Warehouse::from_rows(rows))
+  }
+
+  /// Builds the plan of a diagram parsed beforehand, surrounded by empty cells.
+  pub fn from_rows(rows: Vec<Vec<Cell>>) -> Self {
+    let width = rows.first().map_or(0, Vec::len) + 2;
+    let mut plan = Vec::with_capacity(rows.len() + 2);
+    plan.push(vec![Cell::Empty; width]);
+    for mut row in rows {
       row.push(Cell::Empty);
       row.insert(0, Cell::Empty);
-
-      if plan.is_empty() {
-        plan.push(vec![Cell::Empty; row.len()]);
-      }
       plan.push(row);
     }
+    plan.push(vec![Cell::Empty; width]);
 
-    plan.push(vec![Cell::Empty; plan.last().unwrap().len()]);
-
-    Ok(Warehouse { plan })
+    Warehouse {
+      plan,
+      threshold: ACCESSIBLE_EMPTY_NEIGHBORS,
+    }
   }
 
+  /// Makes rolls accessible once they have `threshold` empty neighbors.
+  pub fn with_threshold(self, threshold: usize) -> Self { Self { threshold, ..self } }
+
+  /// Removes accessible rolls wave after wave until none is left. Stops early
+  /// with the rolls removed so far when the run is cancelled.
   pub fn count_removable_rolls(&mut self) -> u32 {
+    let cancellation = CancellationToken::current();
     let mut removable_rolls = 0;
     let mut q = self.find_empty();
+    let mut wave = 0;
 
-    while !q.is_empty() {
+    while !q.is_empty() && !cancellation.is_cancelled() {
       q = self.process_empty(&q);
       removable_rolls += q.len();
+      wave += 1;
+      debug!(wave, removed = q.len(), total = removable_rolls, "removal wave");
     }
 
     removable_rolls as u32
   }
 
+  /// Counts the rolls accessible before anything is removed, which is the first
+  /// wave of [`Warehouse::count_removable_rolls`].
+  pub fn count_accessible_rolls(&mut self) -> u32 {
+    let q = self.find_empty();
+    self.process_empty(&q).len() as u32
+  }
+
   fn process_empty(&mut self, q: &[(usize, usize)]) -> Vec<(usize, usize)> {
     let max_rows = self.plan.len();
     let max_colls = self.plan[0].len();
@@ -88,7 +115,7 @@ impl Warehouse {
         let Some(nj) = dj.checked_ap(*j, max_colls) else {
           continue;
         };
-        if self.plan[ni][nj].add_empty_neighbor() {
+        if self.plan[ni][nj].add_empty_neighbor(self.threshold) {
           new_q.push((ni, nj));
         }
       }
@@ -120,11 +147,14 @@ mod test {
   #[test]
   fn parse_row() {
     let example_wh = Warehouse {
-      plan: vec![
-        vec![Cell::Empty, Cell::Empty, Cell::Roll(0)],
-        vec![Cell::Roll(0), Cell::Roll(0), Cell::Roll(0)],
-        vec![Cell::Empty, Cell::Empty, Cell::Empty],
+      plan:      vec![
+        vec![Cell::Empty, Cell::Empty, Cell::Empty, Cell::Empty, Cell::Empty],
+        vec![Cell::Empty, Cell::Empty, Cell::Empty, Cell::Roll(0), Cell::Empty],
+        vec![Cell::Empty, Cell::Roll(0), Cell::Roll(0), Cell::Roll(0), Cell::Empty],
+        vec![Cell::Empty, Cell::Empty, Cell::Empty, Cell::Empty, Cell::Empty],
+        vec![Cell::Empty, Cell::Empty, Cell::Empty, Cell::Empty, Cell::Empty],
       ],
+      threshold: ACCESSIBLE_EMPTY_NEIGHBORS,
     };
 
     let buf = Cursor::new("..@\n@@@\n...");
@@ -132,4 +162,12 @@ mod test {
     assert!(parsed.is_ok());
     assert_eq!(parsed.unwrap(), example_wh);
   }
+
+  #[test]
+  fn test_count_accessible_rolls() {
+    let buf = Cursor::new("..@\n@@@\n...");
+    let mut wh = Warehouse::from_read_buf(buf).unwrap();
+
+    assert_eq!(wh.count_accessible_rolls(), 4);
+  }
 }
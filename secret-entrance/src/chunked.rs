@@ -0,0 +1,169 @@
+//! Parallel [`count_zeroes_2`](crate::password::count_zeroes_2) for huge
+//! rotation logs.
+//!
+//! A rotation only ever shifts the dial, so a chunk of rotations can be solved
+//! for every position the dial may enter it at, without knowing where the
+//! previous chunks leave it. Chunks are summarised in parallel, then combined
+//! in order.
+
+use std::thread;
+
+use crate::password::{DialConfig, Rotation};
+
+/// Rotations below which a chunk is not worth a thread of its own.
+const MIN_CHUNK_LEN: usize = 1 << 16;
+
+/// What a chunk of rotations does to the dial, whatever it starts at.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ChunkSummary {
+  /// Clicks the chunk turns the dial right by, modulo its size.
+  offset: usize,
+  /// Clicks landing on the target for every position entering the chunk.
+  counts: Vec<usize>,
+}
+
+impl ChunkSummary {
+  pub fn new(rotations: &[Rotation], config: &DialConfig) -> Self {
+    let size = config.size;
+    // Clicks landing `d` to the right of the entry position, modulo the size:
+    // full turns hit every position once, the rest of a rotation a range of
+    // them, added to `ranges` as differences.
+    let mut full_turns = 0;
+    let mut ranges = vec![0_isize; size + 1];
+    let mut add_range = |from: usize, len: usize| {
+      let to = from + len;
+      ranges[from] += 1;
+      if to <= size {
+        ranges[to] -= 1;
+      } else {
+        ranges[size] -= 1;
+        ranges[0] += 1;
+        ranges[to - size] -= 1;
+      }
+    };
+
+    let mut offset = 0;
+    for rotation in rotations {
+      match *rotation {
+        // The dial stays put, which counts when it stays on the target.
+        Rotation::Left(0) | Rotation::Right(0) => add_range(offset, 1),
+        Rotation::Left(dist) => {
+          full_turns += dist / size;
+          offset = (offset + size - dist % size) % size;
+          add_range(offset, dist % size);
+        }
+        Rotation::Right(dist) => {
+          full_turns += dist / size;
+          add_range((offset + 1) % size, dist % size);
+          offset = (offset + dist) % size;
+        }
+      }
+    }
+
+    let mut hits = Vec::with_capacity(size);
+    let mut running = 0;
+    for difference in &ranges[..size] {
+      running += difference;
+      hits.push(full_turns + running as usize);
+    }
+    let counts = (0..size)
+      .map(|entry| hits[(config.target + size - entry) % size])
+      .collect();
+
+    ChunkSummary { offset, counts }
+  }
+
+  /// Summary of the rotations of `self` followed by those of `next`.
+  pub fn then(self, next: &ChunkSummary) -> Self {
+    let size = self.counts.len();
+    let counts = (0..size)
+      .map(|entry| self.counts[entry] + next.counts[(entry + self.offset) % size])
+      .collect();
+    ChunkSummary {
+      offset: (self.offset + next.offset) % size,
+      counts,
+    }
+  }
+
+  /// Clicks landing on the target when entering the chunk at `entry`.
+  pub fn count(&self, entry: usize) -> usize { self.counts[entry] }
+}
+
+/// Same count as [`count_zeroes_2`](crate::password::count_zeroes_2), with the
+/// rotations split into a chunk per core.
+pub fn count_zeroes_2(rotations: &[Rotation], config: &DialConfig) -> usize {
+  let threads = thread::available_parallelism().map_or(1, usize::from);
+  let chunk_len = rotations.len().div_ceil(threads).max(MIN_CHUNK_LEN);
+  let summaries = thread::scope(|scope| {
+    let workers = rotations
+      .chunks(chunk_len)
+      .map(|chunk| scope.spawn(move || ChunkSummary::new(chunk, config)))
+      .collect::<Vec<_>>();
+    workers
+      .into_iter()
+      .map(|worker| worker.join().expect("chunk thread panicked"))
+      .collect::<Vec<_>>()
+  });
+
+  summaries
+    .into_iter()
+    .reduce(|summary, next| summary.then(&next))
+    .map_or(0, |summary| summary.count(config.start))
+}
+
+#[cfg(test)]
+mod test {
+  use cli_app::Rng;
+
+  use super::*;
+  use crate::password;
+
+  fn random_rotations(rng: &mut Rng, len: usize, max_distance: u64) -> Vec<Rotation> {
+    (0..len)
+      .map(|_| {
+        let dist = rng.range(0..=max_distance) as usize;
+        if rng.chance(0.5) {
+          Rotation::Left(dist)
+        } else {
+          Rotation::Right(dist)
+        }
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_summary_matches_sequential() {
+    let mut rng = Rng::new(1);
+    for config in [
+      DialConfig::default(),
+      DialConfig {
+        size:   7,
+        start:  3,
+        target: 5,
+      },
+      DialConfig {
+        size:   1,
+        start:  0,
+        target: 0,
+      },
+    ] {
+      let rotations = random_rotations(&mut rng, 500, 3 * config.size as u64);
+      let sequential = |start| {
+        let config = DialConfig { start, ..config };
+        password::count_zeroes_2(Box::new(rotations.clone().into_iter()), &config)
+      };
+
+      let whole = ChunkSummary::new(&rotations, &config);
+      let combined = rotations
+        .chunks(37)
+        .map(|chunk| ChunkSummary::new(chunk, &config))
+        .reduce(|summary, next| summary.then(&next))
+        .unwrap();
+      assert_eq!(combined, whole);
+      for start in 0..config.size {
+        assert_eq!(whole.count(start), sequential(start));
+      }
+      assert_eq!(count_zeroes_2(&rotations, &config), sequential(config.start));
+    }
+  }
+}
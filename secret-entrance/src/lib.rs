@@ -0,0 +1,140 @@
+//! Day 1: Secret Entrance.
+//!
+//! The solver functions live in [`password`]; [`App`] wires them into
+//! `cli_app`.
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{Result, bail, ensure};
+use cli_app::{ParseError, Part, Rng, Variant};
+
+use crate::password::DialConfig;
+
+pub mod chunked;
+pub mod lock;
+pub mod password;
+pub mod starts;
+pub mod trace;
+
+#[derive(Clone, Copy)]
+pub struct App {}
+
+impl cli_app::Params for DialConfig {
+  fn set(&mut self, key: &str, value: &str) -> Result<()> {
+    match key {
+      "dial-size" => self.size = cli_app::params::value(key, value)?,
+      "start" => self.start = cli_app::params::value(key, value)?,
+      "target" => self.target = cli_app::params::value(key, value)?,
+      _ => bail!("unknown parameter `{key}`, expected `dial-size`, `start` or `target`."),
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<()> {
+    ensure!(self.size > 0, "`dial-size` must be positive.");
+    ensure!(
+      self.start < self.size && self.target < self.size,
+      "`start` and `target` must be on the dial, below {}.",
+      self.size
+    );
+    Ok(())
+  }
+}
+
+impl cli_app::App for App {
+  type Input = Vec<password::Rotation>;
+  type Output = usize;
+  type Params = DialConfig;
+
+  const DAY: u8 = 1;
+  const NAME: &'static str = "secret-entrance";
+
+  fn parse_input(self, buf: impl BufRead) -> Result<Self::Input, ParseError> { password::parse_rotations(buf) }
+
+  fn solve_part_one(self, input: Self::Input, config: &DialConfig) -> Result<Self::Output> {
+    Ok(password::count_zeroes(Box::new(input.into_iter()), config))
+  }
+
+  fn solve_part_two(self, input: Self::Input, config: &DialConfig) -> Result<Self::Output> {
+    Ok(password::count_zeroes_2(Box::new(input.into_iter()), config))
+  }
+
+  /// Part two can also be solved in parallel chunks, see [`chunked`].
+  fn variants(part: Part) -> Vec<Variant<Self>> {
+    match part {
+      Part::First => vec![Variant::new("default", Self::solve_part_one)],
+      Part::Second => vec![
+        Variant::new("default", Self::solve_part_two),
+        Variant::new("chunked", App::count_zeroes_2_chunked),
+      ],
+      Part::Both => Vec::new(),
+    }
+  }
+}
+
+impl App {
+  fn count_zeroes_2_chunked(self, input: Vec<password::Rotation>, config: &DialConfig) -> Result<usize> {
+    Ok(chunked::count_zeroes_2(&input, config))
+  }
+}
+
+/// Shape of the rotations written by `aoc gen`.
+#[derive(Clone, Debug)]
+pub struct Shape {
+  /// Longest rotation, in clicks.
+  pub max_distance: u64,
+}
+
+impl Default for Shape {
+  fn default() -> Self { Shape { max_distance: 1000 } }
+}
+
+impl cli_app::Params for Shape {
+  fn set(&mut self, key: &str, value: &str) -> Result<()> {
+    match key {
+      "max-distance" => self.max_distance = cli_app::params::value(key, value)?,
+      _ => bail!("unknown parameter `{key}`, expected `max-distance`."),
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<()> {
+    ensure!(self.max_distance > 0, "`max-distance` must be positive.");
+    Ok(())
+  }
+}
+
+/// Writes `size` rotations of random direction and distance.
+impl cli_app::InputGenerator for App {
+  type Shape = Shape;
+
+  fn generate(self, size: usize, shape: &Shape, rng: &mut Rng, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+      let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+      writeln!(out, "{direction}{}", rng.range(1..=shape.max_distance))?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use cli_app::App as _;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_error() {
+    let err = App {}.parse_input(&b"L68\nX30\nR4x"[..]).unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (2, 1, 1));
+
+    let err = App {}.parse_input(&b"L68\nR4x"[..]).unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (2, 2, 2));
+  }
+
+  #[test]
+  fn test_readme_example() { cli_app::examples::check_readme(App {}, include_str!("../README.md")).unwrap() }
+
+  #[test]
+  fn test_generated_input() { cli_app::generate::check(App {}, 1000, 1).unwrap() }
+}
@@ -0,0 +1,239 @@
+//! Combination locks of several dials, each with its own geometry.
+//!
+//! A lock's rotations address their dial by index, `2:L68`; a plain `L68`
+//! turns dial 0, so single-dial inputs are locks of one dial.
+
+use std::io::{self, BufRead, Write};
+
+use cli_app::{Line, ParseError};
+use tracing::debug;
+
+use crate::password::{Dial, DialConfig, Rotation};
+
+/// Most dials a lock may have, as every dial up to the highest index a move
+/// addresses is simulated.
+pub const MAX_DIALS: usize = 1024;
+
+/// A rotation of one of the dials of a lock.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Move {
+  pub dial:     usize,
+  pub rotation: Rotation,
+}
+
+impl TryFrom<&Line> for Move {
+  type Error = ParseError;
+
+  fn try_from(line: &Line) -> Result<Self, ParseError> {
+    let Some((dial, rotation)) = line.text.split_once(':') else {
+      return Ok(Move {
+        dial:     0,
+        rotation: Rotation::try_from(line)?,
+      });
+    };
+    Ok(Move {
+      dial:     dial
+        .parse()
+        .ok()
+        .filter(|&dial| dial < MAX_DIALS)
+        .ok_or_else(|| line.error(0, dial.len(), format!("a dial index like `2`, below {MAX_DIALS}")))?,
+      rotation: Rotation::parse(line, dial.len() + 1, rotation)?,
+    })
+  }
+}
+
+pub fn parse_moves(buf: impl BufRead) -> Result<Vec<Move>, ParseError> {
+  cli_app::lines(buf).map(|line| Move::try_from(&line?)).collect()
+}
+
+/// Parses the `--dial SIZE[,START[,TARGET]]` of the `lock` subcommand. The
+/// start defaults to the middle of the dial, like the puzzle's 50 out of 100,
+/// and the target to 0.
+pub fn parse_dial(s: &str) -> Result<DialConfig, String> {
+  let numbers = s
+    .split(',')
+    .map(|n| n.trim().parse::<usize>().map_err(|_| format!("invalid number `{n}`")))
+    .collect::<Result<Vec<_>, _>>()?;
+  let (size, start, target) = match numbers[..] {
+    [size] => (size, size / 2, 0),
+    [size, start] => (size, start, 0),
+    [size, start, target] => (size, start, target),
+    _ => return Err("expected `SIZE[,START[,TARGET]]`".to_string()),
+  };
+  if size == 0 || start >= size || target >= size {
+    return Err(format!("the start and target must be on a dial of {size} numbers"));
+  }
+  Ok(DialConfig { size, start, target })
+}
+
+/// Dials turned independently of each other.
+pub struct Lock {
+  dials: Vec<Dial>,
+}
+
+/// Zero counts of every dial of a lock, after all its moves.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LockCounts {
+  /// Moves leaving each dial on its target, as counted by
+  /// [`count_zeroes`](crate::password::count_zeroes).
+  pub stops:         Vec<usize>,
+  /// Clicks of each dial landing on its target, as counted by
+  /// [`count_zeroes_2`](crate::password::count_zeroes_2).
+  pub clicks:        Vec<usize>,
+  /// 1-based moves after which every dial was on its target.
+  pub all_on_target: Vec<usize>,
+}
+
+impl Lock {
+  pub fn new(configs: &[DialConfig]) -> Self {
+    Lock {
+      dials: configs.iter().map(|&config| Dial::new(config)).collect(),
+    }
+  }
+
+  pub fn all_on_target(&self) -> bool { self.dials.iter().all(Dial::is_on_target) }
+
+  /// Plays every move, counting the targets hit by each dial. The moves must
+  /// only address dials of the lock, see [`configs`].
+  pub fn simulate(&mut self, moves: &[Move]) -> LockCounts {
+    let mut counts = LockCounts {
+      stops:         vec![0; self.dials.len()],
+      clicks:        vec![0; self.dials.len()],
+      all_on_target: Vec::new(),
+    };
+    for (i, &Move { dial, rotation }) in moves.iter().enumerate() {
+      counts.clicks[dial] += self.dials[dial].apply(&rotation);
+      if self.dials[dial].is_on_target() {
+        counts.stops[dial] += 1;
+        counts.clicks[dial] += 1;
+        if self.all_on_target() {
+          debug!(r#move = i + 1, "all dials on target");
+          counts.all_on_target.push(i + 1);
+        }
+      }
+    }
+    counts
+  }
+}
+
+/// Configuration of every dial the `moves` address: the `dials` given in
+/// order, then `default` for the others.
+pub fn configs(dials: &[DialConfig], default: DialConfig, moves: &[Move]) -> Vec<DialConfig> {
+  let count = moves.iter().map(|mv| mv.dial + 1).max().unwrap_or(1).max(dials.len());
+  (0..count).map(|i| dials.get(i).copied().unwrap_or(default)).collect()
+}
+
+/// Writes the counts of every dial and of the whole lock, followed by the moves
+/// that left every dial on its target.
+pub fn write(configs: &[DialConfig], counts: &LockCounts, out: &mut impl Write) -> io::Result<()> {
+  writeln!(out, "Dial  Size  Start  Target  Stops  Clicks")?;
+  for (dial, config) in configs.iter().enumerate() {
+    writeln!(
+      out,
+      "{dial:>4}  {:>4}  {:>5}  {:>6}  {:>5}  {:>6}",
+      config.size, config.start, config.target, counts.stops[dial], counts.clicks[dial]
+    )?;
+  }
+  writeln!(
+    out,
+    "{:<27}{:>5}  {:>6}",
+    "All",
+    counts.stops.iter().sum::<usize>(),
+    counts.clicks.iter().sum::<usize>()
+  )?;
+
+  writeln!(out)?;
+  match &counts.all_on_target[..] {
+    [] => writeln!(out, "No move left every dial on its target."),
+    moves => {
+      let moves = moves.iter().map(usize::to_string).collect::<Vec<_>>();
+      writeln!(out, "Every dial was on its target after moves {}.", moves.join(", "))
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::password;
+
+  const LOCK: &str = "0:R50\n1:L5\nL100\n1:R10\n0:L0\n";
+
+  fn dials() -> Vec<DialConfig> { vec![parse_dial("100").unwrap(), parse_dial("10,5").unwrap()] }
+
+  #[test]
+  fn test_parse_moves() {
+    let moves = parse_moves(LOCK.as_bytes()).unwrap();
+    assert_eq!(moves[1], Move {
+      dial:     1,
+      rotation: Rotation::Left(5),
+    });
+    assert_eq!(moves[2].dial, 0);
+
+    let err = parse_moves(&b"L68\n2:X30\n"[..]).unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (2, 3, 1));
+    let err = parse_moves(&b"L68\nb:L30\n"[..]).unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (2, 1, 1));
+    let err = parse_moves(&b"99999999999:L1\n"[..]).unwrap_err();
+    assert_eq!((err.line, err.column, err.width), (1, 1, 11));
+  }
+
+  #[test]
+  fn test_parse_dial() {
+    assert_eq!(parse_dial("100"), Ok(DialConfig::default()));
+    assert_eq!(
+      parse_dial("20,3,7"),
+      Ok(DialConfig {
+        size:   20,
+        start:  3,
+        target: 7,
+      })
+    );
+    assert!(parse_dial("10,10").is_err());
+    assert!(parse_dial("10,a").is_err());
+  }
+
+  #[test]
+  fn test_simulate() {
+    let moves = parse_moves(LOCK.as_bytes()).unwrap();
+    let counts = Lock::new(&dials()).simulate(&moves);
+    assert_eq!(counts, LockCounts {
+      stops:         vec![3, 2],
+      clicks:        vec![3, 2],
+      all_on_target: vec![2, 3, 4, 5],
+    });
+
+    let mut table = Vec::new();
+    write(&dials(), &counts, &mut table).unwrap();
+    assert_eq!(
+      String::from_utf8(table).unwrap(),
+      "\
+Dial  Size  Start  Target  Stops  Clicks
+   0   100     50       0      3       3
+   1    10      5       0      2       2
+All                            5       5
+
+Every dial was on its target after moves 2, 3, 4, 5.
+"
+    );
+  }
+
+  #[test]
+  fn test_single_dial() {
+    let example = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+    let moves = parse_moves(example.as_bytes()).unwrap();
+    let rotations = password::parse_rotations(example.as_bytes()).unwrap();
+    let config = DialConfig::default();
+
+    let counts = Lock::new(&configs(&[], config, &moves)).simulate(&moves);
+    assert_eq!(counts.stops, vec![password::count_zeroes(
+      Box::new(rotations.clone().into_iter()),
+      &config
+    )]);
+    assert_eq!(counts.clicks, vec![password::count_zeroes_2(
+      Box::new(rotations.into_iter()),
+      &config
+    )]);
+    assert_eq!(counts.all_on_target.len(), counts.stops[0]);
+  }
+}
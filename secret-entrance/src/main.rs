@@ -1,22 +1,71 @@
-use anyhow::Result;
-use cli_app;
-
-mod password;
+use std::io::{self, BufWriter, Write};
 
-fn main() -> Result<()> { cli_app::run(App {}) }
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use cli_app::{InputArgs, RunArgs};
+use secret_entrance::password::DialConfig;
+use secret_entrance::{App, lock, password, starts, trace};
 
-#[derive(Clone, Copy)]
-struct App {}
+/// Solves the secret entrance, traces its dial step by step, solves it from
+/// every start position or simulates a lock of several dials.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
+struct Cli {
+  #[command(subcommand)]
+  command: Option<Command>,
+  #[command(flatten)]
+  args:    RunArgs,
+}
 
-impl cli_app::App for App {
-  type Input = Box<dyn Iterator<Item = password::Rotation>>;
-  type Output = usize;
+#[derive(Subcommand, Debug)]
+enum Command {
+  /// Write the position of the dial around every rotation.
+  Trace {
+    #[command(flatten)]
+    input:  InputArgs,
+    #[arg(long, value_enum, default_value_t)]
+    format: trace::Format,
+  },
+  /// Solve both parts for every start position of the dial.
+  Starts {
+    #[command(flatten)]
+    input: InputArgs,
+  },
+  /// Count the zeroes of every dial of a lock, whose rotations address a dial
+  /// by index like `2:L68`.
+  Lock {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Geometry of the next dial of the lock, the start defaulting to the
+    /// middle of the dial and the target to 0. Dials without one take the
+    /// dial of `--param`.
+    #[arg(long = "dial", value_name = "SIZE[,START[,TARGET]]", value_parser = lock::parse_dial)]
+    dials: Vec<DialConfig>,
+  },
+}
 
-  fn parse_input(self, buf: std::io::BufReader<std::fs::File>) -> Result<Self::Input> {
-    Ok(Box::new(password::parse_rotations(buf)?))
+fn main() -> Result<()> {
+  let cli = Cli::parse();
+  match cli.command {
+    None => cli_app::run_with(App {}, cli.args),
+    Some(Command::Trace { input, format }) => {
+      let (rotations, config) = input.load(App {})?;
+      let mut out = BufWriter::new(io::stdout().lock());
+      trace::write(password::trace(rotations, &config), format, &mut out)?;
+      Ok(out.flush()?)
+    }
+    Some(Command::Starts { input }) => {
+      let (rotations, config) = input.load(App {})?;
+      let answers = password::count_for_every_start(&rotations, &config);
+      starts::write(&answers, config.start, &mut io::stdout().lock())?;
+      Ok(())
+    }
+    Some(Command::Lock { input, dials }) => {
+      let (moves, default) = input.load_with::<App, _>(|input| lock::parse_moves(input))?;
+      let configs = lock::configs(&dials, default, &moves);
+      let counts = lock::Lock::new(&configs).simulate(&moves);
+      lock::write(&configs, &counts, &mut io::stdout().lock())?;
+      Ok(())
+    }
   }
-
-  fn solve_part_one(self, input: Self::Input) -> Result<Self::Output> { Ok(password::count_zeroes(input)) }
-
-  fn solve_part_two(self, input: Self::Input) -> Result<Self::Output> { Ok(password::count_zeroes_2(input)) }
 }
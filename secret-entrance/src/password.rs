@@ -1,59 +1,117 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fmt;
+use std::io::BufRead;
 
-use anyhow::Result;
+use cli_app::{Line, ParseError};
+use tracing::{debug, trace};
 
-static DIAL_SIZE: usize = 100;
+use crate::chunked::ChunkSummary;
 
-#[derive(Debug, PartialEq)]
+/// Numbers around the dial of the safe.
+pub const DIAL_SIZE: usize = 100;
+/// Number the dial points at before the first rotation.
+pub const DIAL_START: usize = 50;
+/// Number the password counts the dial stopping at, or passing.
+pub const DIAL_TARGET: usize = 0;
+
+/// Geometry of a dial, defaulting to the one of the puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DialConfig {
+  pub size:   usize,
+  pub start:  usize,
+  pub target: usize,
+}
+
+impl Default for DialConfig {
+  fn default() -> Self {
+    DialConfig {
+      size:   DIAL_SIZE,
+      start:  DIAL_START,
+      target: DIAL_TARGET,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Rotation {
   Left(usize),
   Right(usize),
 }
 
-impl From<String> for Rotation {
-  fn from(value: String) -> Self {
-    if value.starts_with('L') {
-      return Rotation::Left(value[1..].parse().unwrap())
+impl Rotation {
+  /// Parses the rotation `token` found at the byte `offset` of `line`.
+  pub(crate) fn parse(line: &Line, offset: usize, token: &str) -> Result<Self, ParseError> {
+    let distance = || {
+      token[1..]
+        .parse()
+        .map_err(|_| line.error(offset + 1, token.len() - 1, "a rotation distance like `68`"))
+    };
+    match token.chars().next() {
+      Some('L') => Ok(Rotation::Left(distance()?)),
+      Some('R') => Ok(Rotation::Right(distance()?)),
+      _ => Err(line.error(offset, 1, "a rotation direction `L` or `R`")),
     }
-    if value.starts_with('R') {
-      return Rotation::Right(value[1..].parse().unwrap())
+  }
+}
+
+impl TryFrom<&Line> for Rotation {
+  type Error = ParseError;
+
+  fn try_from(line: &Line) -> Result<Self, ParseError> { Rotation::parse(line, 0, &line.text) }
+}
+
+impl fmt::Display for Rotation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Rotation::Left(dist) => write!(f, "L{dist}"),
+      Rotation::Right(dist) => write!(f, "R{dist}"),
     }
-    unreachable!()
   }
 }
 
-struct Dial {
-  pos: usize,
+pub(crate) struct Dial {
+  pos:    usize,
+  config: DialConfig,
 }
 
 impl Dial {
-  fn new() -> Self { Dial { pos: 50 } }
+  pub(crate) fn new(config: DialConfig) -> Self {
+    Dial {
+      pos: config.start,
+      config,
+    }
+  }
 
-  fn is_zero(&self) -> bool { self.pos == 0 }
+  pub(crate) fn is_on_target(&self) -> bool { self.pos == self.config.target }
 
-  fn apply(&mut self, rot: &Rotation) -> usize {
-    let curr = self.pos;
-    match rot {
+  /// Turns the dial, returning how many times it passed the target on the way,
+  /// not counting the number it stops at.
+  pub(crate) fn apply(&mut self, rot: &Rotation) -> usize {
+    let size = self.config.size;
+    // Positions relative to the target, which then sits at zero.
+    let curr = (self.pos + size - self.config.target) % size;
+    let (rel, passed) = match rot {
       Rotation::Left(dist) => {
-        let new_pos = DIAL_SIZE + self.pos - dist % DIAL_SIZE;
-        self.pos = new_pos % DIAL_SIZE;
-        if curr > 0 && new_pos < DIAL_SIZE {
-          dist / DIAL_SIZE + 1
+        let new_pos = size + curr - dist % size;
+        if curr == 0 {
+          // Leaving the target, it is only passed again every full turn.
+          (new_pos % size, dist.saturating_sub(1) / size)
+        } else if new_pos < size {
+          (new_pos % size, dist / size + 1)
         } else {
-          dist / DIAL_SIZE
+          (new_pos % size, dist / size)
         }
       }
       Rotation::Right(dist) => {
-        let new_pos = self.pos + dist;
-        self.pos = new_pos % DIAL_SIZE;
-        if self.pos == 0 {
-          new_pos / DIAL_SIZE - 1
+        let new_pos = curr + dist;
+        if new_pos.is_multiple_of(size) {
+          (0, (new_pos / size).saturating_sub(1))
         } else {
-          new_pos / DIAL_SIZE
+          (new_pos % size, new_pos / size)
         }
       }
-    }
+    };
+    self.pos = (rel + self.config.target) % size;
+    passed
   }
 }
 
@@ -65,37 +123,208 @@ impl Dial {
 //   Ok(count_zeroes(Box::new(rotations)))
 // }
 
-pub fn count_zeroes(rotations: Box<dyn Iterator<Item = Rotation>>) -> usize {
-  let mut dial = Dial::new();
+/// Counts the rotations leaving the dial pointing at its target.
+pub fn count_zeroes(rotations: Box<dyn Iterator<Item = Rotation>>, config: &DialConfig) -> usize {
+  let mut dial = Dial::new(*config);
   let mut zeros = 0;
 
   for rotation in rotations {
+    let from = dial.pos;
     dial.apply(&rotation);
-    if dial.is_zero() {
+    trace!(?rotation, from, to = dial.pos, "rotated");
+    if dial.is_on_target() {
       zeros += 1;
+      debug!(zeros, "stopped at target");
     }
   }
   zeros
 }
 
-pub fn count_zeroes_2(rotations: Box<dyn Iterator<Item = Rotation>>) -> usize {
-  let mut dial = Dial::new();
+/// Counts every click of the dial landing on its target, during or at the end
+/// of a rotation.
+pub fn count_zeroes_2(rotations: Box<dyn Iterator<Item = Rotation>>, config: &DialConfig) -> usize {
+  let mut dial = Dial::new(*config);
   let mut zeros = 0;
 
   for rotation in rotations {
-    zeros += dial.apply(&rotation);
-    if dial.is_zero() {
+    let from = dial.pos;
+    let passed = dial.apply(&rotation);
+    trace!(?rotation, from, to = dial.pos, passed, "rotated");
+    zeros += passed;
+    if passed > 0 {
+      debug!(passed, zeros, "passed target");
+    }
+    if dial.is_on_target() {
       zeros += 1;
+      debug!(zeros, "stopped at target");
     }
   }
   zeros
 }
 
-pub fn parse_rotations(buf: BufReader<File>) -> Result<impl Iterator<Item = Rotation>> {
-  Ok(
-    buf
-      .lines()
-      .map(|line| line.expect("cannot read line."))
-      .map(Rotation::from),
-  )
+/// Answers of both parts when the dial starts at `start`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StartAnswers {
+  pub start:    usize,
+  pub part_one: usize,
+  pub part_two: usize,
+}
+
+/// [`count_zeroes`] and [`count_zeroes_2`] for every start position of the
+/// dial at once, instead of replaying the rotations from each of them.
+///
+/// Rotations only shift the dial, so where it stops after every rotation is the
+/// start shifted by the same offset whatever the start: counting the offsets
+/// once tells how many stops land on the target from any start.
+pub fn count_for_every_start(rotations: &[Rotation], config: &DialConfig) -> Vec<StartAnswers> {
+  let size = config.size;
+  let mut stops = vec![0; size];
+  let mut offset = 0;
+  for rotation in rotations {
+    offset = match *rotation {
+      Rotation::Left(dist) => (offset + size - dist % size) % size,
+      Rotation::Right(dist) => (offset + dist) % size,
+    };
+    stops[offset] += 1;
+  }
+
+  let clicks = ChunkSummary::new(rotations, config);
+  (0..size)
+    .map(|start| StartAnswers {
+      start,
+      part_one: stops[(config.target + size - start) % size],
+      part_two: clicks.count(start),
+    })
+    .collect()
+}
+
+/// The dial around a single rotation.
+#[derive(Debug, PartialEq)]
+pub struct Step {
+  pub rotation:  Rotation,
+  pub before:    usize,
+  pub after:     usize,
+  /// Times the dial passed the target during the rotation, as counted by
+  /// [`count_zeroes_2`] besides where it stopped.
+  pub passed:    usize,
+  pub on_target: bool,
+}
+
+/// Replays `rotations` one step at a time.
+pub fn trace(rotations: impl IntoIterator<Item = Rotation>, config: &DialConfig) -> impl Iterator<Item = Step> {
+  let mut dial = Dial::new(*config);
+  rotations.into_iter().map(move |rotation| {
+    let before = dial.pos;
+    let passed = dial.apply(&rotation);
+    Step {
+      rotation,
+      before,
+      after: dial.pos,
+      passed,
+      on_target: dial.is_on_target(),
+    }
+  })
+}
+
+pub fn parse_rotations(buf: impl BufRead) -> Result<Vec<Rotation>, ParseError> {
+  cli_app::lines(buf).map(|line| Rotation::try_from(&line?)).collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn rotations() -> Box<dyn Iterator<Item = Rotation>> {
+    let example = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+    Box::new(parse_rotations(example.as_bytes()).unwrap().into_iter())
+  }
+
+  #[test]
+  fn test_apply_with_target() {
+    let mut dial = Dial::new(DialConfig {
+      size:   10,
+      start:  5,
+      target: 7,
+    });
+    assert_eq!(dial.apply(&Rotation::Right(2)), 0);
+    assert!(dial.is_on_target());
+    assert_eq!(dial.apply(&Rotation::Right(25)), 2);
+    assert_eq!(dial.pos, 2);
+    assert_eq!(dial.apply(&Rotation::Left(16)), 2);
+    assert_eq!(dial.pos, 6);
+  }
+
+  #[test]
+  fn test_apply_full_turns_from_target() {
+    let mut dial = Dial::new(DialConfig {
+      start: 0,
+      ..DialConfig::default()
+    });
+    assert_eq!(dial.apply(&Rotation::Left(100)), 0);
+    assert_eq!(dial.apply(&Rotation::Left(200)), 1);
+    assert_eq!(dial.apply(&Rotation::Right(0)), 0);
+    assert_eq!(dial.apply(&Rotation::Left(0)), 0);
+    assert!(dial.is_on_target());
+  }
+
+  #[test]
+  fn test_trace() {
+    let steps = trace(rotations(), &DialConfig::default()).collect::<Vec<_>>();
+    assert_eq!(steps[2], Step {
+      rotation:  Rotation::Right(48),
+      before:    52,
+      after:     0,
+      passed:    0,
+      on_target: true,
+    });
+    assert_eq!(steps.iter().filter(|step| step.on_target).count(), 3);
+    assert_eq!(
+      steps
+        .iter()
+        .map(|step| step.passed + step.on_target as usize)
+        .sum::<usize>(),
+      6
+    );
+  }
+
+  #[test]
+  fn test_count_for_every_start() {
+    let config = DialConfig {
+      size:   10,
+      start:  0,
+      target: 3,
+    };
+    let example = rotations().collect::<Vec<_>>();
+    let answers = count_for_every_start(&example, &config);
+
+    assert_eq!(answers.len(), 10);
+    for answers in answers {
+      let config = DialConfig {
+        start: answers.start,
+        ..config
+      };
+      assert_eq!(answers.part_one, count_zeroes(rotations(), &config));
+      assert_eq!(answers.part_two, count_zeroes_2(rotations(), &config));
+    }
+  }
+
+  #[test]
+  fn test_count_with_target() {
+    let shifted = DialConfig {
+      start: 60,
+      target: 10,
+      ..DialConfig::default()
+    };
+    // Shifting the start and the target alike shifts every position.
+    assert_eq!(count_zeroes(rotations(), &shifted), 3);
+    assert_eq!(count_zeroes_2(rotations(), &shifted), 6);
+
+    let small = DialConfig {
+      size:   10,
+      start:  5,
+      target: 3,
+    };
+    assert_eq!(count_zeroes(rotations(), &small), 0);
+    assert_eq!(count_zeroes_2(rotations(), &small), 46);
+  }
 }
@@ -0,0 +1,69 @@
+//! `secret-entrance starts`: how the password depends on where the dial
+//! starts.
+
+use std::io::{self, Write};
+
+use crate::password::StartAnswers;
+
+/// Writes a table of the answers for every start, marking the configured one,
+/// followed by the range each part spans.
+pub fn write(answers: &[StartAnswers], configured: usize, out: &mut impl Write) -> io::Result<()> {
+  writeln!(out, "Start  Part one  Part two")?;
+  for answer in answers {
+    let marker = if answer.start == configured {
+      "  (configured)"
+    } else {
+      ""
+    };
+    writeln!(
+      out,
+      "{:>5}  {:>8}  {:>8}{marker}",
+      answer.start, answer.part_one, answer.part_two
+    )?;
+  }
+
+  let range = |part: fn(&StartAnswers) -> usize| {
+    let min = answers.iter().map(part).min().unwrap_or_default();
+    let max = answers.iter().map(part).max().unwrap_or_default();
+    format!("{min} to {max}")
+  };
+  writeln!(
+    out,
+    "\nPart one ranges from {}, part two from {}.",
+    range(|answer| answer.part_one),
+    range(|answer| answer.part_two)
+  )
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_write() {
+    let answers = [
+      StartAnswers {
+        start:    0,
+        part_one: 1,
+        part_two: 12,
+      },
+      StartAnswers {
+        start:    1,
+        part_one: 3,
+        part_two: 6,
+      },
+    ];
+    let mut table = Vec::new();
+    write(&answers, 1, &mut table).unwrap();
+    assert_eq!(
+      String::from_utf8(table).unwrap(),
+      "\
+Start  Part one  Part two
+    0         1        12
+    1         3         6  (configured)
+
+Part one ranges from 1 to 3, part two from 6 to 12.
+"
+    );
+  }
+}
@@ -0,0 +1,87 @@
+//! `secret-entrance trace`: exports every step of the dial, to plot it or
+//! diff it against a reference.
+
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::password::Step;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+  #[default]
+  Csv,
+  /// One JSON object per line.
+  Jsonl,
+}
+
+pub fn write(steps: impl Iterator<Item = Step>, format: Format, out: &mut impl Write) -> io::Result<()> {
+  if format == Format::Csv {
+    writeln!(out, "rotation,before,after,passed,on_target")?;
+  }
+  for step in steps {
+    match format {
+      Format::Csv => writeln!(
+        out,
+        "{},{},{},{},{}",
+        step.rotation, step.before, step.after, step.passed, step.on_target
+      )?,
+      Format::Jsonl => writeln!(
+        out,
+        "{}",
+        json!({
+          "rotation": step.rotation.to_string(),
+          "before": step.before,
+          "after": step.after,
+          "passed": step.passed,
+          "on_target": step.on_target,
+        })
+      )?,
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::password::Rotation;
+
+  fn steps() -> impl Iterator<Item = Step> {
+    [
+      Step {
+        rotation:  Rotation::Left(68),
+        before:    50,
+        after:     82,
+        passed:    1,
+        on_target: false,
+      },
+      Step {
+        rotation:  Rotation::Right(48),
+        before:    52,
+        after:     0,
+        passed:    0,
+        on_target: true,
+      },
+    ]
+    .into_iter()
+  }
+
+  #[test]
+  fn test_write() {
+    let mut csv = Vec::new();
+    write(steps(), Format::Csv, &mut csv).unwrap();
+    assert_eq!(
+      String::from_utf8(csv).unwrap(),
+      "rotation,before,after,passed,on_target\nL68,50,82,1,false\nR48,52,0,0,true\n"
+    );
+
+    let mut jsonl = Vec::new();
+    write(steps(), Format::Jsonl, &mut jsonl).unwrap();
+    assert_eq!(
+      String::from_utf8(jsonl).unwrap().lines().nth(1),
+      Some(r#"{"after":0,"before":52,"on_target":true,"passed":0,"rotation":"R48"}"#)
+    );
+  }
+}